@@ -0,0 +1,83 @@
+use aoc_common::error::AocError;
+use aoc_common::parse;
+use aoc_common::solution::Solution;
+
+pub struct Day1;
+
+fn count_increasing(measurements: &[i32]) -> i32 {
+    let mut count = 0;
+    let mut prev = match measurements.first() {
+        Some(m) => m,
+        None => return 0,
+    };
+
+    for m in measurements {
+        if m > prev {
+            count += 1;
+        }
+        prev = m;
+    }
+    count
+}
+
+fn part1(measurements: &[i32]) -> i32 {
+    count_increasing(measurements)
+}
+
+fn part2(measurements: &[i32]) -> i32 {
+    // we need to post-process the measurements using a "sliding window" of
+    // three-measurements, summed together
+    let n = measurements.len();
+    let mut lines: Vec<i32> = vec![];
+
+    for i in 2..n {
+        let j = i - 1;
+        let k = i - 2;
+
+        let m1 = &measurements[i];
+        let m2 = &measurements[j];
+        let m3 = &measurements[k];
+        lines.push(m1 + m2 + m3);
+    }
+
+    count_increasing(&lines)
+}
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse::lines(input)
+    }
+
+    fn part1(measurements: &Self::Input) -> Result<Self::Part1, AocError> {
+        Ok(part1(measurements))
+    }
+
+    fn part2(measurements: &Self::Input) -> Result<Self::Part2, AocError> {
+        Ok(part2(measurements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+
+    const EXAMPLE: &str = "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+";
+
+    aoc_example!(Day1, EXAMPLE, part1 = 7, part2 = 5);
+}
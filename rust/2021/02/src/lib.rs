@@ -0,0 +1,45 @@
+pub mod parse;
+mod part1;
+mod part2;
+
+use crate::parse::CommandLine;
+use aoc_common::error::AocError;
+use aoc_common::parse::lines_with;
+use aoc_common::solution::Solution;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<CommandLine>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        lines_with(input, CommandLine::parse)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Self::Part1, AocError> {
+        Ok(part1::solve(instructions))
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Self::Part2, AocError> {
+        Ok(part2::solve(instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+
+    const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+    aoc_example!(Day2, EXAMPLE, part1 = 150, part2 = 900);
+}
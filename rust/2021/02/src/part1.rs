@@ -1,9 +1,4 @@
-use crate::parse::{
-    Command,
-    CommandLine,
-    ParseError,
-};
-use crate::utils;
+use crate::parse::{Command, CommandLine};
 
 #[derive(Debug)]
 struct Submarine {
@@ -13,7 +8,10 @@ struct Submarine {
 
 impl Submarine {
     fn new() -> Submarine {
-        Submarine { position: 0, depth: 0 }
+        Submarine {
+            position: 0,
+            depth: 0,
+        }
     }
 
     fn run(&mut self, cmd: &CommandLine) {
@@ -25,20 +23,12 @@ impl Submarine {
     }
 }
 
-
-pub fn main() -> Result<(), ParseError> {
-    println!("Day 2 - Part I");
+pub fn solve(instructions: &[CommandLine]) -> i32 {
     let mut sub = Submarine::new();
-    println!("{:?}", sub);
 
-    let instructions = utils::read_input().iter()
-        .map(|s| CommandLine::parse(s))
-        .collect::<Result<Vec<_>, _>>()?;
-    for i in &instructions {
+    for i in instructions {
         sub.run(i);
     }
-    println!("{:?}", sub);
-    println!("Answer = {}", sub.depth * sub.position);
 
-    Ok(())
+    sub.depth * sub.position
 }
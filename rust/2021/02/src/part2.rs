@@ -1,9 +1,4 @@
-use crate::parse::{
-    Command,
-    CommandLine,
-    ParseError,
-};
-use crate::utils;
+use crate::parse::{Command, CommandLine};
 
 #[derive(Debug)]
 struct Submarine {
@@ -26,26 +21,19 @@ impl Submarine {
             CommandLine(Command::Forward, value) => {
                 self.position += value;
                 self.depth += self.aim * value;
-            },
+            }
             CommandLine(Command::Down, value) => self.aim += value,
             CommandLine(Command::Up, value) => self.aim -= value,
         }
     }
 }
 
-pub fn main() -> Result<(), ParseError>{
-    println!("Day 2 - Part II");
+pub fn solve(instructions: &[CommandLine]) -> i32 {
     let mut sub = Submarine::new();
 
-    let instructions = utils::read_input().iter()
-        .map(|s| CommandLine::parse(s))
-        .collect::<Result<Vec<_>, _>>()?;
-    
-    for i in &instructions {
+    for i in instructions {
         sub.run(i);
     }
-    println!("{:?}", sub);
-    println!("Answer = {}", sub.depth * sub.position);
 
-    Ok(())
+    sub.depth * sub.position
 }
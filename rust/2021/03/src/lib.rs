@@ -0,0 +1,35 @@
+mod utils;
+
+use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<u8>>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        utils::parse_input(input)
+    }
+
+    fn part1(_: &Self::Input) -> Result<Self::Part1, AocError> {
+        Err("Part I has not been solved yet".into())
+    }
+
+    fn part2(_: &Self::Input) -> Result<Self::Part2, AocError> {
+        Err("Part II has not been solved yet".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_width() {
+        assert!(Day3::parse("00100\n1111\n").is_err());
+        assert!(Day3::parse("00100\n11110\n").is_ok());
+    }
+}
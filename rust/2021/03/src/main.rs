@@ -1,13 +0,0 @@
-mod utils;
-mod part1;
-
-fn main() {
-    part1::main();
-    // let s = String::from("00111001");
-    // println!("s = {:?}", s);
-
-    // let parsed: Vec<u32> = s.chars()
-    //     .map(|c| c.to_digit(10).unwrap())
-    //     .collect();
-    // println!("parsed = {:?}", parsed);
-}
@@ -0,0 +1,153 @@
+//! Traces and debugs programs for the CPU of day 10.
+
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+use aoc2022::days::d10::crt::Config;
+use aoc2022::days::d10::trace::{self, Breakpoint, Debugger, Format, TABLE_HEADER};
+use aoc2022::days::d10::{self, asm, Command};
+use aoc_common::{args, image, input};
+
+const USAGE: &str = "\
+usage: cpu trace <program> [--format table|csv]
+       cpu debug <program> [--break <breakpoint>]...
+       cpu screen <program> [--output <image>] [--scale <n>]
+       cpu asm <program>
+
+Runs a program for the CPU of 2022 day 10, either recording every cycle with
+`trace` or stepping through it interactively with `debug`. Breakpoints are
+given as `cycle=<n>` or `x=<value>`, the latter pausing when X changes to it.
+`screen` prints the CRT, or saves it as a .pbm, .ppm or .png image. `asm` checks
+a program and prints it back without comments. Programs may contain labels
+(`name:`) and comments starting with `;`.";
+
+const COMMANDS: &str = "\
+commands:
+  s, step            run a single cycle
+  n, next            run the rest of the instruction in flight
+  c, continue        run until a breakpoint is hit or the program ends
+  b, break <bp>      add a breakpoint, e.g. `break cycle=20` or `break x=7`
+  d, delete          remove every breakpoint
+  r, registers       print the cycle count, registers and next instruction
+  t, trace [csv]     print every cycle run so far
+  screen             print the CRT
+  q, quit            exit the debugger";
+
+/// Prints the state of the CPU, along with the instruction to be executed next
+fn print_registers(debugger: &Debugger<Command>) {
+    let vm = debugger.vm();
+    match debugger.current() {
+        Some(instruction) => println!(
+            "cycle {}, X = {}, next: {instruction}",
+            vm.cycle, vm.registers.x
+        ),
+        None => println!("cycle {}, X = {}, program ended", vm.cycle, vm.registers.x),
+    }
+}
+
+/// Runs a single command of the debugger, returning `false` once it should exit
+fn command(debugger: &mut Debugger<Command>, line: &str) -> bool {
+    let mut words = line.split_whitespace();
+
+    match (words.next(), words.next()) {
+        (None, _) => (),
+        (Some("s" | "step"), _) => match debugger.step() {
+            Some(row) => println!("{TABLE_HEADER}\n{row}"),
+            None => println!("The program has ended"),
+        },
+        (Some("n" | "next"), _) => {
+            println!("{TABLE_HEADER}");
+            for row in debugger.step_instruction() {
+                println!("{row}");
+            }
+        }
+        (Some("c" | "continue"), _) => match debugger.resume() {
+            Some(breakpoint) => {
+                println!("Hit breakpoint {breakpoint}");
+                print_registers(debugger);
+            }
+            None => println!("The program has ended"),
+        },
+        (Some("b" | "break"), Some(s)) => match s.parse::<Breakpoint>() {
+            Ok(breakpoint) => debugger.breakpoints.push(breakpoint),
+            Err(e) => println!("{e}"),
+        },
+        (Some("d" | "delete"), _) => debugger.breakpoints.clear(),
+        (Some("r" | "registers"), _) => print_registers(debugger),
+        (Some("t" | "trace"), format) => {
+            let format = if format == Some("csv") {
+                Format::Csv
+            } else {
+                Format::Table
+            };
+            print!("{}", trace::render(debugger.trace(), format));
+        }
+        (Some("screen"), _) => print!("{}", debugger.crt().render()),
+        (Some("q" | "quit"), _) => return false,
+        _ => println!("{COMMANDS}"),
+    }
+
+    true
+}
+
+fn debug(program: &[Command], breakpoints: Vec<Breakpoint>) -> Result<(), Box<dyn Error>> {
+    let mut debugger = Debugger::new(program);
+    debugger.breakpoints = breakpoints;
+    print_registers(&debugger);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("(cpu) ");
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+        if !command(&mut debugger, &line) {
+            return Ok(());
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [mode, path, flags @ ..] = args else {
+        return Err("Expected a mode and the path of a program".into());
+    };
+    let source = asm::assemble(&input::read_path(path)?)?;
+    let program = &source.instructions;
+
+    let mut format = Format::Table;
+    let mut breakpoints = vec![];
+    let mut output = None;
+    let mut scale = 1;
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-f" | "--format" => format = value.parse()?,
+            "-b" | "--break" => breakpoints.push(value.parse()?),
+            "-o" | "--output" => output = Some(value),
+            "-s" | "--scale" => scale = value.parse()?,
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+
+    match mode.as_str() {
+        "trace" => print!("{}", trace::render(&trace::trace(program), format)),
+        "debug" => debug(program, breakpoints)?,
+        "screen" => {
+            let crt = d10::draw(program, Config::default());
+            match output {
+                Some(path) => image::save(crt.pixels(), path, scale)?,
+                None => print!("{}", crt.render()),
+            }
+        }
+        "asm" => print!("{source}"),
+        _ => return Err(format!("Unknown mode `{mode}`").into()),
+    }
+
+    Ok(())
+}
+
+fn main() {
+    args::main(USAGE, run);
+}
@@ -1,14 +0,0 @@
-use std::env;
-use std::error::Error;
-use std::fs::read_to_string;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let path = &args[1];
-
-    let input = read_to_string(path)?.trim().to_string();
-    let lines: Vec<_> = input.split("\n").into_iter().collect();
-
-    dbg!(lines);
-    Ok(())
-}
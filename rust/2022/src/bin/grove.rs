@@ -0,0 +1,45 @@
+//! Draws the positions of the elves of day 23 after a number of rounds.
+
+use std::error::Error;
+
+use aoc2022::days::d23::{self, Day23};
+use aoc_common::solution::Solution;
+use aoc_common::{args, image, input};
+
+const USAGE: &str = "\
+usage: grove <input> [--rounds <n>] [--output <image>] [--scale <n>]
+
+Simulates the elves of 2022 day 23 spreading out through the grove for the given
+number of rounds (default: 10), then prints the grove or saves it as a .pbm,
+.ppm or .png image.";
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [path, flags @ ..] = args else {
+        return Err("Expected the path of a grove scan".into());
+    };
+    let grove = Day23::parse(&input::read_path(path)?)?;
+
+    let mut rounds = 10;
+    let mut output = None;
+    let mut scale = 1;
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-r" | "--rounds" => rounds = value.parse()?,
+            "-o" | "--output" => output = Some(value),
+            "-s" | "--scale" => scale = value.parse()?,
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+
+    let grid = d23::draw_grove(&d23::simulate(&grove, rounds));
+    match output {
+        Some(path) => image::save(&grid, path, scale)?,
+        None => print!("{}", grid.render('#', '.')),
+    }
+
+    Ok(())
+}
+
+fn main() {
+    args::main(USAGE, run);
+}
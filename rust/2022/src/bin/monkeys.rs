@@ -0,0 +1,176 @@
+//! Inspects the jobs of the monkeys of day 21.
+
+use std::error::Error;
+use std::fs;
+
+use aoc2022::days::d21::query::{Query, Solver};
+use aoc2022::days::d21::{self, dot, validate, Day21, HUMN, ROOT};
+use aoc_common::solution::Solution;
+use aoc_common::{args, input, parse};
+
+const USAGE: &str = "\
+usage: monkeys check <input> [--root <monkey>]
+       monkeys dot <input> [--root <monkey>] [--unknown <monkey>] [--output <file>]
+       monkeys equation <input> [--root <monkey>] [--unknown <monkey>]
+       monkeys query <input> [--root <monkey>] [--unknown <monkey>]
+                     [--eval <monkey>]... [--target <monkey>=<value>]...
+                     [--batch <file>]
+
+Inspects the jobs of the monkeys of 2022 day 21, where the root and the unknown
+default to `root` and `humn`. `check` reports every cycle, reference to an
+undeclared monkey, duplicate definition and monkey that the root never uses.
+`dot` exports the jobs used by the root as a Graphviz digraph, labelled with
+operators and values and with the path from the root to the unknown highlighted.
+`equation` prints the equation in the unknown that holds when the root sees its
+two operands as equal.
+
+`query` answers questions against a single parse of the input: `--eval` prints
+the value of a monkey, and `--target` solves for the value of the unknown that
+makes a monkey yell the given value. A batch file holds one query per line, as
+`eval <monkey>`, `solve <unknown> <monkey>=<value>` or `balance <unknown>
+<root>`, the last solving for the operands of the root to be equal. Without any
+queries, both parts of the puzzle are answered.";
+
+/// Prints every issue with the jobs, failing if any of them prevents evaluation
+fn check(input: &str, flags: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut root = ROOT;
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-r" | "--root" => root = value,
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+
+    let issues = validate::validate(&d21::parse_monkeys(input)?, Some(root));
+    for issue in &issues {
+        let level = if issue.is_error() { "error" } else { "warning" };
+        println!("{level}: {issue}");
+    }
+
+    match issues.iter().filter(|issue| issue.is_error()).count() {
+        0 => Ok(()),
+        n => Err(format!("Found {n} errors").into()),
+    }
+}
+
+/// Prints the graph of the jobs as DOT, or saves it to `--output`
+fn export(input: &str, flags: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut root, mut unknown, mut output) = (ROOT, None, None);
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-r" | "--root" => root = value,
+            "-u" | "--unknown" => unknown = Some(value),
+            "-o" | "--output" => output = Some(value),
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+
+    let graph = Day21::parse(input)?;
+    // humn is only highlighted by default if there is such a monkey
+    let unknown = unknown.or(graph.id(HUMN).map(|_| HUMN));
+    let dot = dot::to_dot(&graph, root, unknown)?;
+    match output {
+        Some(path) => fs::write(path, dot)?,
+        None => print!("{dot}"),
+    }
+
+    Ok(())
+}
+
+/// Prints the equation that the root encodes, in terms of the unknown
+fn equation(input: &str, flags: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut root, mut unknown) = (ROOT, HUMN);
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-r" | "--root" => root = value,
+            "-u" | "--unknown" => unknown = value,
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+
+    let graph = Day21::parse(input)?;
+    println!("{}", Solver::new(&graph).equation(unknown, root)?);
+
+    Ok(())
+}
+
+/// Parses the `<monkey>=<value>` of `--target` into a query solving for `unknown`
+fn target(unknown: &str, goal: &str) -> Result<Query, Box<dyn Error>> {
+    let (target, value) = goal.split_once('=').ok_or(format!(
+        "Expected a target as <monkey>=<value>, found `{goal}`"
+    ))?;
+
+    Ok(Query::Solve {
+        unknown: unknown.to_string(),
+        target: target.to_string(),
+        value: value.parse()?,
+    })
+}
+
+/// Answers each query against the same graph, failing if any of them fails
+fn query(input: &str, flags: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut root, mut unknown) = (ROOT, HUMN);
+    // targets only become queries once the unknown is known
+    let mut pending: Vec<Result<Query, &str>> = vec![];
+    for (flag, value) in args::flags(flags)? {
+        match flag {
+            "-r" | "--root" => root = value,
+            "-u" | "--unknown" => unknown = value,
+            "-e" | "--eval" => pending.push(Ok(Query::Eval(value.to_string()))),
+            "-t" | "--target" => pending.push(Err(value)),
+            "-b" | "--batch" => {
+                let batch = input::read_path(value)?;
+                pending.extend(parse::lines_with(&batch, str::parse)?.into_iter().map(Ok));
+            }
+            _ => return Err(args::unknown(flag)),
+        }
+    }
+    let mut queries = pending
+        .into_iter()
+        .map(|query| query.or_else(|goal| target(unknown, goal)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if queries.is_empty() {
+        queries.push(Query::Eval(root.to_string()));
+        queries.push(Query::Balance {
+            unknown: unknown.to_string(),
+            root: root.to_string(),
+        });
+    }
+
+    let graph = Day21::parse(input)?;
+    let mut solver = Solver::new(&graph);
+    let mut failed = 0;
+    for query in &queries {
+        match solver.answer(query) {
+            Ok(answer) => println!("{query} -> {answer}"),
+            Err(e) => {
+                println!("{query} -> Error: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{n} of {} queries failed", queries.len()).into()),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [mode, path, flags @ ..] = args else {
+        return Err("Expected a mode and the path of an input".into());
+    };
+    let input = input::read_path(path)?;
+
+    match mode.as_str() {
+        "check" => check(&input, flags),
+        "dot" => export(&input, flags),
+        "equation" => equation(&input, flags),
+        "query" => query(&input, flags),
+        _ => Err(format!("Unknown mode `{mode}`").into()),
+    }
+}
+
+fn main() {
+    args::main(USAGE, run);
+}
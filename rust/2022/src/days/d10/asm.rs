@@ -0,0 +1,190 @@
+//! An assembler and disassembler for programs of the CPU. Each line holds at most one
+//! instruction, which may be preceded by any number of labels (`name:`) and followed
+//! by a comment starting with `;`. Blank lines and comments are ignored.
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::error::AocError;
+use aoc_common::parse::ParseError;
+
+use super::Command;
+
+/// An assembled program, along with the labels that mark positions within it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Program {
+    pub instructions: Vec<Command>,
+    /// Each label and the index of the instruction it precedes, in order of position
+    pub labels: Vec<(String, usize)>,
+}
+
+impl Program {
+    /// The index of the instruction marked by the label `name`
+    pub fn label(&self, name: &str) -> Option<usize> {
+        self.labels
+            .iter()
+            .find(|(label, _)| label == name)
+            .map(|&(_, i)| i)
+    }
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Assembles the `source` of a program, reporting the line and column of the first
+/// unknown mnemonic, bad operand, unexpected token or invalid label
+pub fn assemble(source: &str) -> Result<Program, AocError> {
+    let mut program = Program::default();
+
+    for (i, text) in source.lines().enumerate() {
+        let line = i + 1;
+        // the code is kept as the byte range `start..end` of the line, so that errors
+        // point at the right column
+        let end = text
+            .split_once(';')
+            .map_or(text, |(code, _)| code)
+            .trim_end()
+            .len();
+        let mut start = end - text[..end].trim_start().len();
+
+        // labels are only recognised ahead of the instruction
+        while let Some((label, _)) = text[start..end].split_once(':') {
+            let name = label.trim_end();
+            if !is_label(name) {
+                let error = ParseError::Invalid("label", name.to_string());
+                return Err(AocError::parse(line, text, error.at(start)));
+            }
+            if program.label(name).is_some() {
+                let error = ParseError::Syntax(format!("Duplicate label `{name}`"));
+                return Err(AocError::parse(line, text, error.at(start)));
+            }
+
+            program
+                .labels
+                .push((name.to_string(), program.instructions.len()));
+            start = end - text[start + label.len() + 1..end].trim_start().len();
+        }
+
+        let code = &text[start..end];
+        if !code.is_empty() {
+            let command = code
+                .parse()
+                .map_err(|e: ParseError| AocError::parse(line, text, e.offset_by(start)))?;
+            program.instructions.push(command);
+        }
+    }
+
+    Ok(program)
+}
+
+impl FromStr for Program {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assemble(s)
+    }
+}
+
+/// Disassembles the program into source that assembles back to the same program, with
+/// each label on a line of its own
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut labels = self.labels.iter().peekable();
+
+        for i in 0..=self.instructions.len() {
+            while let Some((label, _)) = labels.next_if(|(_, at)| *at == i) {
+                writeln!(f, "{label}:")?;
+            }
+            if let Some(instruction) = self.instructions.get(i) {
+                writeln!(f, "{instruction}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Command::*;
+    use super::*;
+
+    const SOURCE: &str = "\
+; raise X to 4, then lower it below zero
+start: noop
+    addx 3 ; X = 4
+lower:
+  addx -5
+end:
+";
+
+    #[test]
+    fn test_assemble() {
+        let program = assemble(SOURCE).unwrap();
+
+        assert_eq!(program.instructions, vec![Noop, Addx(3), Addx(-5)]);
+        assert_eq!(program.label("lower"), Some(2));
+        assert_eq!(program.label("end"), Some(3));
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = assemble(SOURCE).unwrap();
+        let source = program.to_string();
+
+        assert_eq!(source, "start:\nnoop\naddx 3\nlower:\naddx -5\nend:\n");
+        assert_eq!(assemble(&source).unwrap(), program);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let error = |s: &str| match assemble(s).unwrap_err() {
+            AocError::Parse {
+                line,
+                column,
+                error,
+                ..
+            } => (line, column, error),
+            e => panic!("expected a parse error, found {e:?}"),
+        };
+
+        assert_eq!(
+            error("noop\nmulx 3"),
+            (2, 1, ParseError::Invalid("mnemonic", "mulx".to_string()))
+        );
+        assert_eq!(
+            error("  addx three"),
+            (1, 8, ParseError::Invalid("operand", "three".to_string()))
+        );
+        assert_eq!(
+            error("noop 1 ; comment"),
+            (1, 6, ParseError::Extra("1".to_string()))
+        );
+        assert_eq!(error("addx"), (1, 5, ParseError::Missing("operand")));
+        assert_eq!(
+            error("a:\nnoop\n a: noop"),
+            (3, 2, ParseError::Syntax("Duplicate label `a`".to_string()))
+        );
+        assert_eq!(
+            error("a: addx a"),
+            (1, 9, ParseError::Invalid("operand", "a".to_string()))
+        );
+        assert_eq!(
+            error("addx 1 1"),
+            (1, 8, ParseError::Extra("1".to_string()))
+        );
+        assert_eq!(
+            error("noop: noop noop"),
+            (1, 12, ParseError::Extra("noop".to_string()))
+        );
+        assert_eq!(
+            error("a:\n\u{3000}a: noop"),
+            (2, 2, ParseError::Syntax("Duplicate label `a`".to_string()))
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::grid::Grid;
+use aoc_common::point::Point;
+
+use super::vm::{Observer, Registers};
+
+/// The geometry of a CRT, along with the cycles during which the signal strength is
+/// sampled. The default is the 40x6 screen of the puzzle, sampled during the 20th
+/// cycle and every 40 cycles after that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub width: usize,
+    pub height: usize,
+    /// The number of pixels covered by the sprite, which is centred on the `X` register
+    pub sprite_width: u32,
+    /// The first cycle during which the signal is sampled
+    pub sample_start: u32,
+    /// The number of cycles between samples, where `0` samples only once
+    pub sample_interval: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: 40,
+            height: 6,
+            sprite_width: 3,
+            sample_start: 20,
+            sample_interval: 40,
+        }
+    }
+}
+
+impl Config {
+    /// The number of cycles it takes to draw the whole screen
+    pub fn frame(&self) -> u32 {
+        (self.width * self.height) as u32
+    }
+
+    /// Returns `true` if the signal is sampled during the given `cycle` of the frame
+    pub fn is_sample(&self, cycle: u32) -> bool {
+        cycle >= self.sample_start
+            && cycle <= self.frame()
+            && (cycle - self.sample_start).is_multiple_of(self.sample_interval)
+    }
+}
+
+/// The CRT screen, which draws a single pixel per cycle from left to right and top to
+/// bottom. A pixel is lit if the sprite, centred on the `X` register, overlaps the
+/// pixel being drawn.
+#[derive(Debug, Clone)]
+pub struct Crt {
+    config: Config,
+    pixels: Grid<bool>,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl Crt {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            pixels: Grid::new(config.width, config.height, false),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn pixels(&self) -> &Grid<bool> {
+        &self.pixels
+    }
+
+    /// The position of the pixel drawn during the given `cycle`, or `None` once the
+    /// beam has moved past the last row of the screen
+    pub fn position(&self, cycle: u32) -> Option<Point> {
+        let i = (cycle - 1) as usize;
+        let (column, row) = (i.checked_rem(self.config.width)?, i / self.config.width);
+        (row < self.config.height).then(|| Point::new(column as i32, row as i32))
+    }
+
+    /// The columns covered by the sprite when the `X` register holds the value `x`
+    pub fn sprite(&self, x: i32) -> RangeInclusive<i32> {
+        let width = self.config.sprite_width as i32;
+        let start = x - (width - 1) / 2;
+        start..=start + width - 1
+    }
+
+    /// Renders the screen as lines of text, using `#` for lit pixels and `.` otherwise
+    pub fn render(&self) -> String {
+        self.pixels.render('#', '.')
+    }
+}
+
+impl Observer for Crt {
+    fn cycle(&mut self, cycle: u32, registers: &Registers) {
+        // cycles beyond the last row of the screen are not drawn
+        if let Some(p) = self.position(cycle) {
+            let lit = self.sprite(registers.x).contains(&p.x);
+            self.pixels.set(p, lit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_samples() {
+        let config = Config::default();
+        let samples: Vec<u32> = (1..=300).filter(|&c| config.is_sample(c)).collect();
+        assert_eq!(samples, vec![20, 60, 100, 140, 180, 220]);
+
+        let config = Config {
+            sample_start: 5,
+            sample_interval: 0,
+            ..config
+        };
+        assert!(config.is_sample(5) && !config.is_sample(10));
+    }
+
+    #[test]
+    fn test_custom_geometry() {
+        let mut crt = Crt::new(Config {
+            width: 4,
+            height: 2,
+            sprite_width: 1,
+            ..Config::default()
+        });
+        for cycle in 1..=10 {
+            crt.cycle(cycle, &Registers { x: 2 });
+        }
+
+        assert_eq!(crt.sprite(2), 2..=2);
+        assert_eq!(crt.position(5), Some(Point::new(0, 1)));
+        assert_eq!(crt.position(9), None);
+        assert_eq!(crt.render(), "..#.\n..#.\n");
+    }
+}
@@ -0,0 +1,381 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::ocr;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::solution::Solution;
+
+use crt::{Config, Crt};
+use vm::{Instruction, Observer, Registers, Vm};
+
+pub mod asm;
+pub mod crt;
+pub mod trace;
+pub mod vm;
+
+pub struct Day10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Noop,
+    Addx(i32),
+}
+
+/// Parses a single instruction, without any label or comment
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        // errors are located at the token at fault, or the end of `s` if one is missing
+        let at = |token: &str| parse::offset_of(s, token);
+        let missing = |what| ParseError::Missing(what).at(s.len());
+
+        let command = match tokens.next().ok_or_else(|| missing("instruction"))? {
+            "noop" => Self::Noop,
+            "addx" => {
+                let operand = tokens.next().ok_or_else(|| missing("operand"))?;
+                let i = operand.parse().map_err(|_| {
+                    ParseError::Invalid("operand", operand.to_string()).at(at(operand))
+                })?;
+                Self::Addx(i)
+            }
+            mnemonic => {
+                let error = ParseError::Invalid("mnemonic", mnemonic.to_string());
+                return Err(error.at(at(mnemonic)));
+            }
+        };
+
+        match tokens.next() {
+            Some(token) => Err(ParseError::Extra(token.to_string()).at(at(token))),
+            None => Ok(command),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Noop => write!(f, "noop"),
+            Self::Addx(i) => write!(f, "addx {i}"),
+        }
+    }
+}
+
+impl Instruction for Command {
+    fn cycles(&self) -> u32 {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
+        }
+    }
+
+    fn execute(&self, registers: &mut Registers) {
+        match self {
+            Self::Noop => (),
+            Self::Addx(i) => registers.x += i,
+        }
+    }
+}
+
+/// Samples the signal strength (the cycle number multiplied by `X`) during the cycles
+/// of the frame given by the sampling schedule of a CRT [`Config`]
+#[derive(Debug)]
+pub struct SignalSampler {
+    config: Config,
+    strengths: Vec<i32>,
+}
+
+impl SignalSampler {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            strengths: vec![],
+        }
+    }
+
+    pub fn strengths(&self) -> &[i32] {
+        &self.strengths
+    }
+}
+
+impl Observer for SignalSampler {
+    fn cycle(&mut self, cycle: u32, registers: &Registers) {
+        if self.config.is_sample(cycle) {
+            self.strengths.push(cycle as i32 * registers.x);
+        }
+    }
+}
+
+/// Runs the `program`, returning the sum of the signal strengths sampled according to
+/// the `config`
+pub fn signal_strength(program: &[Command], config: Config) -> i32 {
+    let mut sampler = SignalSampler::new(config);
+    Vm::new().run(program, &mut [&mut sampler]);
+
+    sampler.strengths().iter().sum()
+}
+
+/// Runs the `program` on a CRT with the given `config`, returning the screen it drew
+pub fn draw(program: &[Command], config: Config) -> Crt {
+    let mut crt = Crt::new(config);
+    Vm::new().run(program, &mut [&mut crt]);
+
+    crt
+}
+
+impl Solution for Day10 {
+    type Input = Vec<Command>;
+    type Part1 = i32;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(asm::assemble(input)?.instructions)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError> {
+        Ok(signal_strength(input, Config::default()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError> {
+        let crt = draw(input, Config::default());
+        // a screen that cannot be read as letters is left to be read by eye
+        Ok(ocr::read(crt.pixels()).map_or_else(|_| Answer::Image(crt.render()), Answer::String))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+    use Command::*;
+
+    #[test]
+    fn test_parse_addx_pos() {
+        let inp = "addx 1".parse::<Command>();
+        let exp = Ok(Addx(1));
+        assert_eq!(exp, inp);
+    }
+
+    #[test]
+    fn test_parse_addx_neg() {
+        let inp = "addx -15".parse::<Command>();
+        let exp = Ok(Addx(-15));
+        assert_eq!(exp, inp);
+    }
+
+    #[test]
+    fn test_parse_noop() {
+        let inp = "noop".parse::<Command>();
+        let exp = Ok(Noop);
+        assert_eq!(exp, inp);
+    }
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    const IMAGE: &str = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+
+    aoc_example!(Day10, EXAMPLE, part1 = 13140, part2 = IMAGE);
+
+    /// Records the value of `X` during every cycle
+    #[derive(Default)]
+    struct Recorder(Vec<i32>);
+
+    impl Observer for Recorder {
+        fn cycle(&mut self, _: u32, registers: &Registers) {
+            self.0.push(registers.x);
+        }
+    }
+
+    #[test]
+    fn test_vm_cycles() {
+        let mut vm = Vm::new();
+        let mut recorder = Recorder::default();
+        vm.run(&[Noop, Addx(3), Addx(-5)], &mut [&mut recorder]);
+
+        assert_eq!(recorder.0, vec![1, 1, 1, 4, 4]);
+        assert_eq!((vm.cycle, vm.registers.x), (5, -1));
+    }
+
+    #[test]
+    fn test_signal_schedule() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+        let config = Config {
+            sample_start: 20,
+            sample_interval: 0,
+            ..Config::default()
+        };
+
+        assert_eq!(signal_strength(&program, config), 420);
+    }
+
+    #[test]
+    fn test_crt_render() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+        let mut crt = Crt::default();
+        Vm::new().run(&program, &mut [&mut crt]);
+
+        assert_eq!(crt.render(), IMAGE);
+        // the example draws stripes rather than letters, so the image is kept as is
+        assert_eq!(
+            Day10::part2(&program).unwrap(),
+            Answer::Image(IMAGE.to_string())
+        );
+    }
+}
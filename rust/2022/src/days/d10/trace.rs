@@ -0,0 +1,318 @@
+//! Tracing and step-by-step debugging of programs running on the [`Vm`]. Every cycle
+//! is recorded along with the instruction in flight, the value of the `X` register
+//! before and after the cycle, and what the CRT drew during it.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_common::parse::ParseError;
+use aoc_common::point::Point;
+
+use super::crt::{Config, Crt};
+use super::vm::{Instruction, Vm};
+
+/// A pixel drawn by the CRT during a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pixel {
+    pub position: Point,
+    pub lit: bool,
+}
+
+/// The state of the CPU and CRT during a single cycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRow {
+    pub cycle: u32,
+    /// The instruction in flight during the cycle
+    pub instruction: String,
+    /// The value of `X` during the cycle
+    pub x_before: i32,
+    /// The value of `X` once the cycle has completed
+    pub x_after: i32,
+    /// The pixel drawn during the cycle, if the beam is still on the screen
+    pub pixel: Option<Pixel>,
+    /// The columns covered by the sprite during the cycle
+    pub sprite: RangeInclusive<i32>,
+}
+
+pub const TABLE_HEADER: &str = "cycle  instruction  x before  x after  pixel       sprite";
+pub const CSV_HEADER: &str =
+    "cycle,instruction,x_before,x_after,pixel_column,pixel_row,lit,sprite_start,sprite_end";
+
+impl TraceRow {
+    /// Formats the row as a line of CSV, leaving the pixel fields empty for cycles
+    /// that are not drawn
+    pub fn to_csv(&self) -> String {
+        let pixel = match self.pixel {
+            Some(Pixel { position, lit }) => format!("{},{},{lit}", position.x, position.y),
+            None => ",,".to_string(),
+        };
+
+        format!(
+            "{},{},{},{},{pixel},{},{}",
+            self.cycle,
+            self.instruction,
+            self.x_before,
+            self.x_after,
+            self.sprite.start(),
+            self.sprite.end()
+        )
+    }
+}
+
+/// Formats the row as a line of the table headed by [`TABLE_HEADER`]
+impl fmt::Display for TraceRow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pixel = match self.pixel {
+            Some(Pixel { position, lit }) => {
+                let c = if lit { '#' } else { '.' };
+                format!("({},{}) {c}", position.x, position.y)
+            }
+            None => "-".to_string(),
+        };
+
+        write!(
+            f,
+            "{:>5}  {:<11}  {:>8}  {:>7}  {:<10}  {}..{}",
+            self.cycle,
+            self.instruction,
+            self.x_before,
+            self.x_after,
+            pixel,
+            self.sprite.start(),
+            self.sprite.end()
+        )
+    }
+}
+
+/// The format in which a full trace is output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            _ => Err(ParseError::Invalid("trace format", s.to_string())),
+        }
+    }
+}
+
+/// Renders the rows of a trace, along with a header, in the given format
+pub fn render(rows: &[TraceRow], format: Format) -> String {
+    let (header, rows): (_, Vec<String>) = match format {
+        Format::Table => (TABLE_HEADER, rows.iter().map(|r| r.to_string()).collect()),
+        Format::Csv => (CSV_HEADER, rows.iter().map(|r| r.to_csv()).collect()),
+    };
+
+    let mut s = format!("{header}\n");
+    for row in rows {
+        s.push_str(&row);
+        s.push('\n');
+    }
+    s
+}
+
+/// A condition on which the [`Debugger`] pauses execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Pauses after the given cycle
+    Cycle(u32),
+    /// Pauses after a cycle in which the `X` register changed to the given value
+    X(i32),
+}
+
+impl Breakpoint {
+    fn hit(&self, row: &TraceRow) -> bool {
+        match *self {
+            Self::Cycle(cycle) => row.cycle == cycle,
+            Self::X(x) => row.x_before != x && row.x_after == x,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Cycle(cycle) => write!(f, "cycle={cycle}"),
+            Self::X(x) => write!(f, "x={x}"),
+        }
+    }
+}
+
+/// Parses a breakpoint of the form `cycle=<n>` or `x=<value>`
+impl FromStr for Breakpoint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::Invalid("breakpoint", s.to_string());
+
+        match s.split_once('=') {
+            Some(("cycle", n)) => n.parse().map(Self::Cycle).map_err(|_| invalid()),
+            Some(("x", x)) => x.parse().map(Self::X).map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Runs a program one cycle at a time on a [`Vm`] connected to a [`Crt`], recording
+/// each cycle and pausing on any of its breakpoints
+#[derive(Debug)]
+pub struct Debugger<'a, I> {
+    program: &'a [I],
+    /// The index of the instruction in flight
+    pc: usize,
+    vm: Vm,
+    crt: Crt,
+    trace: Vec<TraceRow>,
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+impl<'a, I: Instruction + fmt::Display> Debugger<'a, I> {
+    pub fn new(program: &'a [I]) -> Self {
+        Self::with_config(program, Config::default())
+    }
+
+    /// Creates a debugger whose CPU drives a CRT with the given `config`
+    pub fn with_config(program: &'a [I], config: Config) -> Self {
+        Self {
+            program,
+            pc: 0,
+            vm: Vm::new(),
+            crt: Crt::new(config),
+            trace: vec![],
+            breakpoints: vec![],
+        }
+    }
+
+    pub fn vm(&self) -> &Vm {
+        &self.vm
+    }
+
+    pub fn crt(&self) -> &Crt {
+        &self.crt
+    }
+
+    /// Every cycle that has been executed so far
+    pub fn trace(&self) -> &[TraceRow] {
+        &self.trace
+    }
+
+    /// The instruction that will be executed during the next cycle
+    pub fn current(&self) -> Option<&I> {
+        self.program.get(self.pc)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Executes a single cycle, returning its record, or `None` if the program has ended
+    pub fn step(&mut self) -> Option<&TraceRow> {
+        let program = self.program;
+        let instruction = program.get(self.pc)?;
+        let cycle = self.vm.cycle + 1;
+        let x_before = self.vm.registers.x;
+
+        if self.vm.tick(instruction, &mut [&mut self.crt]) {
+            self.pc += 1;
+        }
+
+        let pixel = self.crt.position(cycle).map(|position| Pixel {
+            position,
+            lit: self.crt.pixels().get(position) == Some(&true),
+        });
+        self.trace.push(TraceRow {
+            cycle,
+            instruction: instruction.to_string(),
+            x_before,
+            x_after: self.vm.registers.x,
+            pixel,
+            sprite: self.crt.sprite(x_before),
+        });
+
+        self.trace.last()
+    }
+
+    /// Executes the remaining cycles of the instruction in flight
+    pub fn step_instruction(&mut self) -> &[TraceRow] {
+        let (start, pc) = (self.trace.len(), self.pc);
+        while self.pc == pc && self.step().is_some() {}
+
+        &self.trace[start..]
+    }
+
+    /// Runs until a breakpoint is hit, returning it, or until the program ends
+    pub fn resume(&mut self) -> Option<Breakpoint> {
+        while self.step().is_some() {
+            let row = self.trace.last()?;
+            if let Some(&breakpoint) = self.breakpoints.iter().find(|b| b.hit(row)) {
+                return Some(breakpoint);
+            }
+        }
+
+        None
+    }
+}
+
+/// Runs the whole `program`, returning the record of every cycle
+pub fn trace<I: Instruction + fmt::Display>(program: &[I]) -> Vec<TraceRow> {
+    let mut debugger = Debugger::new(program);
+    while debugger.step().is_some() {}
+
+    debugger.trace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Command::{self, *};
+    use super::*;
+
+    const PROGRAM: &[Command] = &[Noop, Addx(3), Addx(-5)];
+
+    #[test]
+    fn test_trace() {
+        let rows = trace(PROGRAM);
+        let x: Vec<_> = rows.iter().map(|r| (r.x_before, r.x_after)).collect();
+
+        assert_eq!(x, vec![(1, 1), (1, 1), (1, 4), (4, 4), (4, -1)]);
+        assert_eq!(rows[2].instruction, "addx 3");
+        assert_eq!(rows[2].sprite, 0..=2);
+        assert_eq!(
+            rows[3].pixel,
+            Some(Pixel {
+                position: Point::new(3, 0),
+                lit: true
+            })
+        );
+        assert_eq!(rows[3].to_csv(), "4,addx -5,4,4,3,0,true,3,5");
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = Debugger::new(PROGRAM);
+        debugger.breakpoints = vec![Breakpoint::X(4), "cycle=5".parse().unwrap()];
+
+        assert_eq!(debugger.resume(), Some(Breakpoint::X(4)));
+        assert_eq!(debugger.vm().cycle, 3);
+        assert_eq!(debugger.current(), Some(&Addx(-5)));
+        assert_eq!(debugger.resume(), Some(Breakpoint::Cycle(5)));
+        assert!(debugger.is_finished());
+        assert_eq!(debugger.resume(), None);
+    }
+
+    #[test]
+    fn test_step_instruction() {
+        let mut debugger = Debugger::new(PROGRAM);
+        debugger.step();
+
+        assert_eq!(debugger.step_instruction().len(), 2);
+        assert_eq!(debugger.vm().registers.x, 4);
+    }
+}
@@ -0,0 +1,82 @@
+//! A small cycle-accurate virtual machine. Each instruction declares how many cycles
+//! it takes and its effect on the registers, which is applied once its final cycle
+//! has completed. Observers are notified during every cycle, which is when the
+//! values of the registers are sampled by the signal and CRT circuits.
+
+/// The registers of the CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i32,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self { x: 1 }
+    }
+}
+
+/// An instruction that can be executed by the [`Vm`]
+pub trait Instruction {
+    /// The number of cycles the instruction takes to complete
+    fn cycles(&self) -> u32;
+
+    /// Applies the effect of the instruction to the registers, at the end of its final cycle
+    fn execute(&self, registers: &mut Registers);
+}
+
+/// Receives the state of the CPU *during* each cycle, before the current instruction
+/// has taken effect
+pub trait Observer {
+    /// Called once per cycle, where the first cycle is `1`
+    fn cycle(&mut self, cycle: u32, registers: &Registers);
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Vm {
+    pub registers: Registers,
+    /// The number of cycles that have completed
+    pub cycle: u32,
+    /// The number of cycles the instruction in flight has been executing for
+    busy: u32,
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances by a single cycle of the `instruction` in flight, notifying each observer.
+    /// Returns `true` if this was the final cycle of the instruction, in which case it
+    /// has taken effect and the next instruction can be started.
+    pub fn tick<I: Instruction>(
+        &mut self,
+        instruction: &I,
+        observers: &mut [&mut dyn Observer],
+    ) -> bool {
+        self.cycle += 1;
+        self.busy += 1;
+        for observer in observers.iter_mut() {
+            observer.cycle(self.cycle, &self.registers);
+        }
+
+        if self.busy < instruction.cycles() {
+            return false;
+        }
+
+        instruction.execute(&mut self.registers);
+        self.busy = 0;
+        true
+    }
+
+    /// Executes a single instruction, notifying each observer during every cycle it takes
+    pub fn step<I: Instruction>(&mut self, instruction: &I, observers: &mut [&mut dyn Observer]) {
+        while !self.tick(instruction, observers) {}
+    }
+
+    /// Executes every instruction of the `program` in order
+    pub fn run<I: Instruction>(&mut self, program: &[I], observers: &mut [&mut dyn Observer]) {
+        for instruction in program {
+            self.step(instruction, observers);
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::u64 as pu64,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+
+use aoc_common::error::AocError;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::solution::Solution;
+
+pub struct Day19;
+
+#[derive(Clone, Debug)]
+struct Ore(u64);
+
+#[derive(Clone, Debug)]
+struct Clay(u64);
+
+#[derive(Clone, Debug)]
+struct Obsidian(u64);
+
+#[derive(Clone, Debug)]
+struct Geode(u64);
+
+#[derive(Clone, Debug)]
+struct Resources {
+    ore: Ore,
+    clay: Clay,
+    obsidian: Obsidian,
+    geode: Geode,
+}
+
+impl Resources {
+    fn new() -> Self {
+        Self {
+            ore: Ore(0),
+            clay: Clay(0),
+            obsidian: Obsidian(0),
+            geode: Geode(0),
+        }
+    }
+
+    /// Collects what the robots of the `fleet` harvest over the given number of `minutes`
+    fn collect(&mut self, fleet: &Fleet, minutes: u64) {
+        self.ore.0 += fleet.ore * minutes;
+        self.clay.0 += fleet.clay * minutes;
+        self.obsidian.0 += fleet.obsidian * minutes;
+        self.geode.0 += fleet.geode * minutes;
+    }
+
+    /// Returns the number of minutes the `fleet` needs to harvest enough resources to
+    /// build the given `robot` from the blueprint, `bp`, or `None` if it never will
+    fn minutes_until_affordable(
+        &self,
+        robot: &Robot,
+        bp: &Blueprint,
+        fleet: &Fleet,
+    ) -> Option<u64> {
+        let (ore, clay, obsidian) = bp.cost(robot);
+
+        [
+            (ore, self.ore.0, fleet.ore),
+            (clay, self.clay.0, fleet.clay),
+            (obsidian, self.obsidian.0, fleet.obsidian),
+        ]
+        .into_iter()
+        .map(
+            |(cost, available, rate)| match cost.saturating_sub(available) {
+                0 => Some(0),
+                _ if rate == 0 => None,
+                missing => Some(missing.div_ceil(rate)),
+            },
+        )
+        .try_fold(0, |wait, minutes| Some(wait.max(minutes?)))
+    }
+
+    /// Describes the lack of resources to build the given `robot` from the blueprint, `bp`
+    fn shortage(&self, robot: &Robot, bp: &Blueprint) -> AocError {
+        AocError::solve(format!(
+            "Not enough resources available to build a {robot:?} robot from blueprint {}: {self:?}",
+            bp.id
+        ))
+    }
+
+    /// Deducts the necessary resources to purchase the given `robot` from the provided
+    /// blueprint, `bp`. If there are not enough resources available, an error is returned.
+    fn purchase(&mut self, robot: &Robot, bp: &Blueprint) -> Result<(), AocError> {
+        match robot {
+            Robot::Ore => {
+                let Ore(cost) = bp.ore;
+                let Ore(available) = self.ore;
+
+                if available >= cost {
+                    let remaining = available - cost;
+                    self.ore = Ore(remaining);
+                } else {
+                    return Err(self.shortage(robot, bp));
+                }
+            }
+            Robot::Clay => {
+                let Ore(cost) = bp.clay;
+                let Ore(available) = self.ore;
+
+                if available >= cost {
+                    let remaining = available - cost;
+                    self.ore = Ore(remaining);
+                } else {
+                    return Err(self.shortage(robot, bp));
+                }
+            }
+            Robot::Obsidian => {
+                let (Ore(ore_cost), Clay(clay_cost)) = bp.obsidian;
+                let Ore(ore_available) = self.ore;
+                let Clay(clay_available) = self.clay;
+
+                if (ore_available >= ore_cost) & (clay_available >= clay_cost) {
+                    let rem_ore = ore_available - ore_cost;
+                    let rem_clay = clay_available - clay_cost;
+                    self.ore = Ore(rem_ore);
+                    self.clay = Clay(rem_clay);
+                } else {
+                    return Err(self.shortage(robot, bp));
+                }
+            }
+            Robot::Geode => {
+                let (Ore(ore_cost), Obsidian(obsidian_cost)) = bp.geode;
+                let Ore(ore_available) = self.ore;
+                let Obsidian(obsidian_available) = self.obsidian;
+
+                if (ore_available >= ore_cost) & (obsidian_available >= obsidian_cost) {
+                    let rem_ore = ore_available - ore_cost;
+                    let rem_obsidian = obsidian_available - obsidian_cost;
+                    self.ore = Ore(rem_ore);
+                    self.obsidian = Obsidian(rem_obsidian);
+                } else {
+                    return Err(self.shortage(robot, bp));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Robot {
+    /// Every kind of robot, the most valuable first so that good builds are found early
+    const ALL: [Robot; 4] = [Robot::Geode, Robot::Obsidian, Robot::Clay, Robot::Ore];
+}
+
+/// The number of robots of each kind
+#[derive(Clone, Debug)]
+struct Fleet {
+    ore: u64,
+    clay: u64,
+    obsidian: u64,
+    geode: u64,
+}
+
+impl Fleet {
+    fn add(&mut self, robot: &Robot) {
+        match robot {
+            Robot::Ore => self.ore += 1,
+            Robot::Clay => self.clay += 1,
+            Robot::Obsidian => self.obsidian += 1,
+            Robot::Geode => self.geode += 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Blueprint {
+    id: u64,
+    ore: Ore,
+    clay: Ore,
+    obsidian: (Ore, Clay),
+    geode: (Ore, Obsidian),
+}
+
+impl Blueprint {
+    /// The ore, clay and obsidian needed to build the given `robot`
+    fn cost(&self, robot: &Robot) -> (u64, u64, u64) {
+        match robot {
+            Robot::Ore => (self.ore.0, 0, 0),
+            Robot::Clay => (self.clay.0, 0, 0),
+            Robot::Obsidian => (self.obsidian.0 .0, self.obsidian.1 .0, 0),
+            Robot::Geode => (self.geode.0 .0, 0, self.geode.1 .0),
+        }
+    }
+
+    /// Returns `true` if another `robot` could be of any use to the `fleet`. Only one robot
+    /// is built per minute, so there is no point harvesting more of a resource per minute
+    /// than the most that any robot costs.
+    fn worth_building(&self, robot: &Robot, fleet: &Fleet) -> bool {
+        match robot {
+            Robot::Ore => {
+                let most = [self.ore.0, self.clay.0, self.obsidian.0 .0, self.geode.0 .0];
+                fleet.ore < most.into_iter().max().unwrap_or(0)
+            }
+            Robot::Clay => fleet.clay < self.obsidian.1 .0,
+            Robot::Obsidian => fleet.obsidian < self.geode.1 .0,
+            Robot::Geode => true,
+        }
+    }
+}
+
+/// Searches every order of building robots with the `fleet` and `resources` at hand and
+/// `time_left` minutes to go, raising `best` to the most geodes opened. Rather than
+/// deciding minute by minute, each step waits until the next robot can be afforded.
+fn search(
+    bp: &Blueprint,
+    fleet: &Fleet,
+    resources: &Resources,
+    time_left: u64,
+    best: &mut u64,
+) -> Result<(), AocError> {
+    // building nothing more still opens geodes with the robots already built
+    let Geode(opened) = resources.geode;
+    let idle = opened + fleet.geode * time_left;
+    *best = (*best).max(idle);
+
+    // even a new geode robot every remaining minute cannot beat the best found so far
+    if idle + time_left * time_left.saturating_sub(1) / 2 <= *best {
+        return Ok(());
+    }
+
+    for robot in &Robot::ALL {
+        if !bp.worth_building(robot, fleet) {
+            continue;
+        }
+        let Some(wait) = resources.minutes_until_affordable(robot, bp, fleet) else {
+            continue;
+        };
+        // a robot finished in the last minute has no time left to harvest anything
+        if wait + 1 >= time_left {
+            continue;
+        }
+
+        let mut resources = resources.clone();
+        resources.collect(fleet, wait);
+        // spend the resources at the top of the minute, and only add the new robot to the
+        // fleet once the minute's harvest is collected
+        resources.purchase(robot, bp)?;
+        resources.collect(fleet, 1);
+        let mut fleet = fleet.clone();
+        fleet.add(robot);
+
+        search(bp, &fleet, &resources, time_left - wait - 1, best)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the largest number of geodes that can be opened with the blueprint, `bp`
+/// within the given `time_limit`
+fn geodes(bp: &Blueprint, time_limit: u64) -> Result<u64, AocError> {
+    // we have one Ore collecting robot to begin
+    let fleet = Fleet {
+        ore: 1,
+        clay: 0,
+        obsidian: 0,
+        geode: 0,
+    };
+
+    // and have no resources initally
+    let mut best = 0;
+    search(bp, &fleet, &Resources::new(), time_limit, &mut best)?;
+    Ok(best)
+}
+
+/// Parses a blueprint in the format:
+///
+/// `Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot
+/// costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.`
+fn parse_blueprint(s: &str) -> IResult<&str, Blueprint> {
+    let (s, id) = delimited(tag("Blueprint "), pu64, tag(":"))(s)?;
+    let (s, ore) = delimited(tag(" Each ore robot costs "), pu64, tag(" ore."))(s)?;
+    let (s, clay) = delimited(tag(" Each clay robot costs "), pu64, tag(" ore."))(s)?;
+    let (s, obsidian) = delimited(
+        tag(" Each obsidian robot costs "),
+        separated_pair(pu64, tag(" ore and "), pu64),
+        tag(" clay."),
+    )(s)?;
+    let (s, geode) = delimited(
+        tag(" Each geode robot costs "),
+        separated_pair(pu64, tag(" ore and "), pu64),
+        tag(" obsidian."),
+    )(s)?;
+
+    let bp = Blueprint {
+        id,
+        ore: Ore(ore),
+        clay: Ore(clay),
+        obsidian: (Ore(obsidian.0), Clay(obsidian.1)),
+        geode: (Ore(geode.0), Obsidian(geode.1)),
+    };
+    Ok((s, bp))
+}
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse::lines_with(input, |line| match parse_blueprint(line) {
+            Ok(("", bp)) => Ok(bp),
+            Ok((rest, _)) => Err(ParseError::Extra(rest.to_string()).at(line.len() - rest.len())),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let error = ParseError::Invalid("blueprint", e.input.to_string());
+                Err(error.at(line.len() - e.input.len()))
+            }
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::Missing("blueprint")),
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError> {
+        input.iter().map(|bp| Ok(bp.id * geodes(bp, 24)?)).sum()
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError> {
+        input.iter().take(3).map(|bp| geodes(bp, 32)).product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+
+    #[test]
+    fn test_parse_blueprint() {
+        let s = "Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+            Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+        let (_, bp) = parse_blueprint(s).unwrap();
+
+        assert_eq!(bp.id, 2);
+        assert_eq!(bp.ore.0, 2);
+        assert_eq!(bp.clay.0, 3);
+        assert_eq!((bp.obsidian.0 .0, bp.obsidian.1 .0), (3, 8));
+        assert_eq!((bp.geode.0 .0, bp.geode.1 .0), (3, 12));
+    }
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn test_geodes() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(geodes(&input[0], 24).unwrap(), 9);
+        assert_eq!(geodes(&input[1], 24).unwrap(), 12);
+    }
+
+    aoc_example!(Day19, EXAMPLE, part1 = 33, part2 = 56 * 62);
+}
@@ -0,0 +1,146 @@
+//! Exports the monkeys' jobs as a Graphviz DOT digraph, so the structure that `solve`
+//! works on can be rendered with standard tools, e.g. `dot -Tsvg`.
+
+use aoc_common::error::AocError;
+
+use super::graph::{Graph, Node, NodeId};
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+/// Every node used by `root`, directly or indirectly, in depth-first order
+fn reachable(graph: &Graph, root: NodeId) -> Vec<NodeId> {
+    let mut seen = vec![false; graph.len()];
+    let (mut stack, mut order) = (vec![root], vec![]);
+    seen[root] = true;
+
+    while let Some(id) = stack.pop() {
+        order.push(id);
+        // pushed in reverse so that the left operand is visited first
+        for child in graph.node(id).children().rev() {
+            if !seen[child] {
+                seen[child] = true;
+                stack.push(child);
+            }
+        }
+    }
+
+    order
+}
+
+/// The DOT identifier of a node: the name of its monkey, followed by its id for a
+/// subexpression of a job
+fn key(graph: &Graph, id: NodeId) -> String {
+    if graph.is_monkey(id) {
+        graph.name(id).to_string()
+    } else {
+        format!("{}.{id}", graph.name(id))
+    }
+}
+
+/// Writes the graph of the jobs used by `root` as DOT. Each node is labelled with the
+/// name of its monkey (unless it is a subexpression of a job), its operator and its value
+/// (`?` where it is undefined). When an `unknown` is given, every node and edge on a
+/// path from `root` to it is highlighted.
+pub fn to_dot(graph: &Graph, root: &str, unknown: Option<&str>) -> Result<String, AocError> {
+    let root = graph.require(root)?;
+    let path = match unknown {
+        Some(name) => Some(graph.dependents(graph.require(name)?)),
+        None => None,
+    };
+    let on_path = |id| path.as_ref().is_some_and(|path| path.contains(id));
+
+    let mut dot = String::from("digraph monkeys {\n");
+    // keeps the operands of each job in order, left to right
+    dot.push_str("    graph [ordering=out];\n");
+    dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+    let nodes = reachable(graph, root);
+    for &id in &nodes {
+        let mut lines = vec![];
+        if graph.is_monkey(id) {
+            lines.push(graph.name(id).to_string());
+        }
+        match graph.node(id) {
+            Node::Num(i) => lines.push(i.to_string()),
+            Node::Ref(_) => {}
+            Node::Neg(_) => lines.push("-".to_string()),
+            Node::Op(op, _, _) => lines.push(op.to_string()),
+        }
+        if !matches!(graph.node(id), Node::Num(_)) {
+            match graph.value(id) {
+                Ok(value) => lines.push(format!("= {value}")),
+                Err(_) => lines.push("= ?".to_string()),
+            }
+        }
+
+        let style = if on_path(id) {
+            format!(", {HIGHLIGHT}")
+        } else {
+            String::new()
+        };
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\"{style}];\n",
+            key(graph, id),
+            lines.join("\\n")
+        ));
+    }
+
+    for &id in &nodes {
+        for child in graph.node(id).children() {
+            let style = if on_path(id) && on_path(child) {
+                format!(" [{HIGHLIGHT}]")
+            } else {
+                String::new()
+            };
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\"{style};\n",
+                key(graph, id),
+                key(graph, child)
+            ));
+        }
+    }
+
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::d21::Job;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_to_dot() {
+        let vars = [
+            "root: a + b",
+            "a: humn / c",
+            "b: c - 2",
+            "c: 0",
+            "humn: 4",
+            "d: 1",
+        ]
+        .iter()
+        .map(|line| {
+            let job = Job::from_str(line).unwrap();
+            (job.name, job.expr)
+        })
+        .collect();
+        let graph = Graph::compile(&vars).unwrap();
+        let dot = to_dot(&graph, "root", Some("humn")).unwrap();
+
+        assert!(dot.starts_with("digraph monkeys {\n"));
+        assert!(dot.contains("\"root\" [label=\"root\\n+\\n= ?\", color=red, penwidth=2];"));
+        assert!(dot.contains("\"a\" [label=\"a\\n/\\n= ?\", color=red, penwidth=2];"));
+        assert!(dot.contains("\"b\" [label=\"b\\n-\\n= -2\"];"));
+        assert!(dot.contains("\"b.6\" [label=\"2\"];"));
+        assert!(dot.contains("\"b\" -> \"b.6\";"));
+        assert!(dot.contains("\"humn\" [label=\"humn\\n4\", color=red, penwidth=2];"));
+        assert!(dot.contains("\"root\" -> \"a\" [color=red, penwidth=2];"));
+        assert!(dot.contains("\"a\" -> \"c\";"));
+        assert!(dot.contains("\"root\" -> \"b\";"));
+        assert!(!dot.contains("\"d\""));
+
+        assert!(to_dot(&graph, "root", Some("x")).is_err());
+    }
+}
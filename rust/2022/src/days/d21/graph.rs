@@ -0,0 +1,427 @@
+//! The monkeys' jobs compiled into an indexed DAG. Every monkey is a node with an
+//! integer id, as is every subexpression of a job other than a reference to a monkey,
+//! and the nodes are kept in topological order (every node after the nodes it refers
+//! to) so the whole graph can be evaluated in a single bottom-up pass.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::OnceLock;
+
+use aoc_common::error::AocError;
+
+use super::rational::{ArithmeticError, Rational};
+use super::{Expr, Ref};
+
+/// The index of a node within a [`Graph`]
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl Op {
+    pub fn apply(self, lhs: Rational, rhs: Rational) -> Result<Rational, ArithmeticError> {
+        match self {
+            Self::Add => lhs.checked_add(rhs),
+            Self::Sub => lhs.checked_sub(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+            Self::Div => lhs.checked_div(rhs),
+            Self::Rem => lhs.checked_rem(rhs),
+            Self::Pow => lhs.checked_pow(rhs),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Pow => "^",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Node {
+    Num(i64),
+    /// The value of another node, for a job that is just the name of another monkey
+    Ref(NodeId),
+    Neg(NodeId),
+    Op(Op, NodeId, NodeId),
+}
+
+impl Node {
+    /// The nodes this node refers to, from left to right
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = NodeId> {
+        let (lhs, rhs) = match *self {
+            Self::Num(_) => (None, None),
+            Self::Ref(id) | Self::Neg(id) => (Some(id), None),
+            Self::Op(_, lhs, rhs) => (Some(lhs), Some(rhs)),
+        };
+        lhs.into_iter().chain(rhs)
+    }
+}
+
+/// Why a node has no value: an arithmetic error in the job of the node `at`, which
+/// every node that depends on it inherits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undefined {
+    pub error: ArithmeticError,
+    pub at: NodeId,
+}
+
+/// A fixed-size set of node ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet(Vec<u64>);
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.0
+            .get(i / 64)
+            .is_some_and(|word| word & (1 << (i % 64)) != 0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// The name of each monkey, whose ids come before those of any subexpression
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    nodes: Vec<Node>,
+    /// The id of the monkey whose job each node belongs to
+    owners: Vec<NodeId>,
+    /// Every node id in topological order
+    order: Vec<NodeId>,
+    /// The value of every node, evaluated on first use
+    values: OnceLock<Vec<Result<Rational, Undefined>>>,
+    /// The first node whose dependents were asked for, along with its dependents
+    dependents: OnceLock<(NodeId, BitSet)>,
+}
+
+impl Graph {
+    /// Compiles the jobs of the monkeys into a graph, failing if a job refers to an
+    /// undeclared monkey or the jobs depend on each other in a cycle
+    pub fn compile(vars: &HashMap<String, Expr>) -> Result<Self, AocError> {
+        // ids are assigned in order of name so that compiling is deterministic
+        let mut names: Vec<String> = vars.keys().cloned().collect();
+        names.sort();
+        let ids: HashMap<String, NodeId> = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.clone(), id))
+            .collect();
+
+        let mut graph = Self {
+            nodes: vec![Node::Num(0); names.len()],
+            owners: (0..names.len()).collect(),
+            names,
+            ids,
+            order: vec![],
+            values: OnceLock::new(),
+            dependents: OnceLock::new(),
+        };
+        for id in 0..graph.names.len() {
+            graph.nodes[id] = graph.lower(id, &vars[&graph.names[id]])?;
+        }
+
+        graph.order = topological_order(&graph.nodes).map_err(|id| {
+            AocError::solve(format!("Cycle detected involving {}", graph.name(id)))
+        })?;
+        Ok(graph)
+    }
+
+    /// Lowers an expression in the job of the monkey `owner` to a node, adding a node for
+    /// each of its subexpressions
+    fn lower(&mut self, owner: NodeId, expr: &Expr) -> Result<Node, AocError> {
+        Ok(match expr {
+            Expr::Num(i) => Node::Num(*i),
+            Expr::Ref(name) => Node::Ref(self.resolve(name)?),
+            Expr::Neg(operand) => Node::Neg(self.operand(owner, operand)?),
+            Expr::Op(op, lhs, rhs) => {
+                Node::Op(*op, self.operand(owner, lhs)?, self.operand(owner, rhs)?)
+            }
+        })
+    }
+
+    /// Returns the node for an operand, which is the monkey itself for a reference
+    fn operand(&mut self, owner: NodeId, expr: &Expr) -> Result<NodeId, AocError> {
+        if let Expr::Ref(name) = expr {
+            return self.resolve(name);
+        }
+
+        let node = self.lower(owner, expr)?;
+        self.nodes.push(node);
+        self.owners.push(owner);
+        Ok(self.nodes.len() - 1)
+    }
+
+    fn resolve(&self, Ref(name): &Ref) -> Result<NodeId, AocError> {
+        self.require(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Looks up the id of the monkey called `name`, failing if there is no such monkey
+    pub fn require(&self, name: &str) -> Result<NodeId, AocError> {
+        self.id(name)
+            .ok_or(AocError::solve(format!("Undeclared variable: {name}")))
+    }
+
+    /// The name of the monkey whose job the node is, or is part of
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[self.owners[id]]
+    }
+
+    /// Returns `true` if the node is a monkey, rather than a subexpression of a job
+    pub fn is_monkey(&self, id: NodeId) -> bool {
+        id < self.names.len()
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// Every node id, ordered so that each node comes after the nodes it refers to
+    pub fn order(&self) -> &[NodeId] {
+        &self.order
+    }
+
+    /// Evaluates every node exactly in a single pass, so that a node is only undefined
+    /// if its own job or one it depends on fails. Divisions that do not divide evenly are
+    /// kept as fractions.
+    fn values(&self) -> &[Result<Rational, Undefined>] {
+        self.values.get_or_init(|| {
+            let mut values = vec![Ok(Rational::ZERO); self.nodes.len()];
+
+            for &id in &self.order {
+                let undefined = |e| Undefined { error: e, at: id };
+                values[id] = match self.nodes[id] {
+                    Node::Num(i) => Ok(i.into()),
+                    Node::Ref(other) => values[other],
+                    Node::Neg(operand) => {
+                        values[operand].and_then(|v: Rational| v.checked_neg().map_err(undefined))
+                    }
+                    Node::Op(op, lhs, rhs) => values[lhs]
+                        .and_then(|l| values[rhs].and_then(|r| op.apply(l, r).map_err(undefined))),
+                };
+            }
+
+            values
+        })
+    }
+
+    /// The exact value of the node, or why it has none
+    pub fn result(&self, id: NodeId) -> Result<Rational, Undefined> {
+        self.values()[id]
+    }
+
+    /// The exact value of the node, failing with the arithmetic error that left it
+    /// undefined
+    pub fn value(&self, id: NodeId) -> Result<Rational, AocError> {
+        self.result(id).map_err(|e| self.error(e))
+    }
+
+    /// Describes why a node is undefined, naming the monkey where the error happened
+    pub fn error(&self, Undefined { error, at }: Undefined) -> AocError {
+        AocError::solve(format!("{error} in {}", self.name(at)))
+    }
+
+    /// Evaluates the monkey called `name`, failing unless its value is an integer
+    pub fn eval(&self, name: &str) -> Result<i64, AocError> {
+        let id = self.require(name)?;
+        integer(name, self.value(id)?)
+    }
+
+    /// The set of nodes whose value depends on the node `target`, including itself. The
+    /// set is cached for the first target asked for, as only one node is ever treated as
+    /// the unknown of a graph.
+    pub fn dependents(&self, target: NodeId) -> Cow<'_, BitSet> {
+        let (cached, set) = self
+            .dependents
+            .get_or_init(|| (target, self.find_dependents(target)));
+
+        if *cached == target {
+            Cow::Borrowed(set)
+        } else {
+            Cow::Owned(self.find_dependents(target))
+        }
+    }
+
+    fn find_dependents(&self, target: NodeId) -> BitSet {
+        let mut set = BitSet::new(self.nodes.len());
+        set.insert(target);
+
+        for &id in &self.order {
+            if self.nodes[id].children().any(|c| set.contains(c)) {
+                set.insert(id);
+            }
+        }
+
+        set
+    }
+}
+
+/// Converts the value of `name` to an integer, failing if it is a fraction or too large
+pub fn integer(name: &str, value: Rational) -> Result<i64, AocError> {
+    value.to_integer().ok_or_else(|| {
+        AocError::solve(format!(
+            "The value of {name} is {value}, which is not an integer within range"
+        ))
+    })
+}
+
+/// Sorts the nodes with Kahn's algorithm, returning a node on a cycle if there is one
+fn topological_order(nodes: &[Node]) -> Result<Vec<NodeId>, NodeId> {
+    let mut parents = vec![vec![]; nodes.len()];
+    let mut pending = vec![0; nodes.len()];
+    for (id, node) in nodes.iter().enumerate() {
+        for child in node.children() {
+            parents[child].push(id);
+            pending[id] += 1;
+        }
+    }
+
+    let mut queue: VecDeque<NodeId> = (0..nodes.len()).filter(|&id| pending[id] == 0).collect();
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(id) = queue.pop_front() {
+        order.push(id);
+        for &parent in &parents[id] {
+            pending[parent] -= 1;
+            if pending[parent] == 0 {
+                queue.push_back(parent);
+            }
+        }
+    }
+
+    match (0..nodes.len()).find(|&id| pending[id] > 0) {
+        Some(id) => Err(id),
+        None => Ok(order),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::d21::Job;
+    use std::str::FromStr;
+
+    fn graph(lines: &[&str]) -> Result<Graph, AocError> {
+        let vars = lines
+            .iter()
+            .map(|line| {
+                let job = Job::from_str(line).unwrap();
+                (job.name, job.expr)
+            })
+            .collect();
+        Graph::compile(&vars)
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let g = graph(&["root: a + b", "a: b * c", "b: 2", "c: 3"]).unwrap();
+        let position = |name| g.order().iter().position(|&id| id == g.id(name).unwrap());
+
+        assert!(position("b") < position("a") && position("c") < position("a"));
+        assert!(position("a") < position("root"));
+        assert_eq!(g.eval("root").unwrap(), 8);
+    }
+
+    #[test]
+    fn test_dependents() {
+        let g = graph(&["root: a + b", "a: humn * c", "b: 2", "c: 3", "humn: 1"]).unwrap();
+        let deps = g.dependents(g.id("humn").unwrap());
+        let names: Vec<_> = (0..g.len())
+            .filter(|&id| deps.contains(id))
+            .map(|id| g.name(id))
+            .collect();
+
+        assert_eq!(names, vec!["a", "humn", "root"]);
+
+        // the set for the first target is cached, and any other is still computed
+        assert!(matches!(
+            g.dependents(g.id("humn").unwrap()),
+            Cow::Borrowed(_)
+        ));
+        let deps = g.dependents(g.id("a").unwrap());
+        assert!(deps.contains(g.id("root").unwrap()) && !deps.contains(g.id("humn").unwrap()));
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(graph(&["root: a + b", "a: 1"]).is_err());
+        assert!(graph(&["root: a + b", "a: root - b", "b: 1"]).is_err());
+    }
+
+    #[test]
+    fn test_eval_exact() {
+        let g = graph(&["root: a * b", "a: c / b", "b: 2", "c: 3"]).unwrap();
+        assert_eq!(g.eval("root").unwrap(), 3);
+        assert!(g.eval("a").is_err());
+
+        let g = graph(&["root: a / b", "a: 1", "b: a - a"]).unwrap();
+        assert_eq!(
+            g.eval("root").unwrap_err().to_string(),
+            "Division by zero in root"
+        );
+    }
+
+    #[test]
+    fn test_eval_partial() {
+        // z is never used by root, so its division by zero does not matter
+        let g = graph(&["root: a + b", "a: 1", "b: 2", "z: b / zero", "zero: 0"]).unwrap();
+        assert_eq!(g.eval("root").unwrap(), 3);
+        assert_eq!(
+            g.eval("z").unwrap_err().to_string(),
+            "Division by zero in z"
+        );
+
+        // the error is reported where it happened, not where it was noticed
+        let g = graph(&["root: a + 1", "a: b % zero", "b: 1", "zero: 0"]).unwrap();
+        assert_eq!(
+            g.eval("root").unwrap_err().to_string(),
+            "Division by zero in a"
+        );
+    }
+
+    #[test]
+    fn test_subexpressions() {
+        let g = graph(&["root: (a + 1) * -b ^ 2 % 7", "a: b", "b: 3"]).unwrap();
+        // ((a + 1) * -(b ^ 2)) % 7, with the sign of the dividend
+        assert_eq!(g.eval("root").unwrap(), -1);
+        assert_eq!(g.eval("a").unwrap(), 3);
+
+        // every operand in the job of root, other than a reference, is a node of its own
+        assert_eq!(g.len(), 10);
+        assert!((3..g.len()).all(|id| !g.is_monkey(id) && g.name(id) == "root"));
+    }
+}
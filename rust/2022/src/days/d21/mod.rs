@@ -0,0 +1,595 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, char, digit1, one_of, space0},
+    combinator::{all_consuming, cut, map, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::{fold_many0, many0_count},
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+
+use aoc_common::error::AocError;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::solution::Solution;
+
+use graph::{Graph, Op};
+use query::Solver;
+
+pub mod dot;
+pub mod graph;
+pub mod query;
+pub mod rational;
+pub mod symbolic;
+pub mod validate;
+
+pub struct Day21;
+
+/// The monkey whose value answers part one, and whose operands must be equal in part two
+pub const ROOT: &str = "root";
+/// The monkey whose value is unknown in part two
+pub const HUMN: &str = "humn";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ref(String);
+
+/// An arithmetic expression over literals and the names of other monkeys
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expr {
+    Num(i64),
+    Ref(Ref),
+    Neg(Box<Expr>),
+    Op(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn op(op: Op, lhs: Expr, rhs: Expr) -> Self {
+        Self::Op(op, Box::new(lhs), Box::new(rhs))
+    }
+
+    /// The names of the monkeys this expression depends on, from left to right
+    pub fn refs(&self) -> Vec<&str> {
+        let (mut stack, mut refs) = (vec![self], vec![]);
+        while let Some(expr) = stack.pop() {
+            match expr {
+                Self::Num(_) => {}
+                Self::Ref(Ref(name)) => refs.push(name.as_str()),
+                Self::Neg(operand) => stack.push(operand),
+                Self::Op(_, lhs, rhs) => {
+                    stack.push(rhs);
+                    stack.push(lhs);
+                }
+            }
+        }
+
+        refs
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Formats the expression with every compound operand in parentheses
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operand = |f: &mut fmt::Formatter, e: &Expr| match e {
+            Self::Num(i) if *i < 0 => write!(f, "({e})"),
+            Self::Neg(_) | Self::Op(..) => write!(f, "({e})"),
+            _ => write!(f, "{e}"),
+        };
+
+        match self {
+            Self::Num(i) => write!(f, "{i}"),
+            Self::Ref(Ref(name)) => write!(f, "{name}"),
+            Self::Neg(e) => {
+                write!(f, "-")?;
+                operand(f, e)
+            }
+            Self::Op(op, lhs, rhs) => {
+                operand(f, lhs)?;
+                write!(f, " {op} ")?;
+                operand(f, rhs)
+            }
+        }
+    }
+}
+
+/// The name of a monkey, e.g. `root` or `humn`
+fn name(s: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        alt((alpha1, tag("_"))),
+        many0_count(alt((alphanumeric1, tag("_")))),
+    ))(s)
+}
+
+/// A literal, a name or an expression in parentheses. A missing closing parenthesis
+/// is the only failure reported as [`ErrorKind::Char`].
+fn atom(s: &str) -> IResult<&str, Expr> {
+    delimited(
+        space0,
+        alt((
+            delimited(char('('), sum, cut(char(')'))),
+            map_res(digit1, |i: &str| i.parse().map(Expr::Num)),
+            map(name, |name| Expr::Ref(Ref(name.to_string()))),
+        )),
+        space0,
+    )(s)
+}
+
+/// An atom raised to a power, which is right associative and binds tighter than
+/// negation, so `-a ^ -b ^ c` is `-(a ^ (-(b ^ c)))`
+fn power(s: &str) -> IResult<&str, Expr> {
+    let (s, base) = atom(s)?;
+    let (s, exp) = opt(preceded(char('^'), cut(unary)))(s)?;
+
+    match exp {
+        Some(exp) => Ok((s, Expr::op(Op::Pow, base, exp))),
+        None => Ok((s, base)),
+    }
+}
+
+fn unary(s: &str) -> IResult<&str, Expr> {
+    let (s, minus) = opt(preceded(space0, char('-')))(s)?;
+    if minus.is_none() {
+        return power(s);
+    }
+
+    // negative literals are kept as literals, so `-5` is still a number
+    match cut(unary)(s)? {
+        (s, Expr::Num(i)) => Ok((s, Expr::Num(-i))),
+        (s, e) => Ok((s, Expr::Neg(Box::new(e)))),
+    }
+}
+
+fn product(s: &str) -> IResult<&str, Expr> {
+    let (s, first) = unary(s)?;
+    fold_many0(
+        pair(one_of("*/%"), cut(unary)),
+        move || first.clone(),
+        |lhs, (op, rhs)| {
+            let op = match op {
+                '*' => Op::Mul,
+                '/' => Op::Div,
+                _ => Op::Rem,
+            };
+            Expr::op(op, lhs, rhs)
+        },
+    )(s)
+}
+
+fn sum(s: &str) -> IResult<&str, Expr> {
+    let (s, first) = product(s)?;
+    fold_many0(
+        pair(one_of("+-"), cut(product)),
+        move || first.clone(),
+        |lhs, (op, rhs)| {
+            let op = if op == '+' { Op::Add } else { Op::Sub };
+            Expr::op(op, lhs, rhs)
+        },
+    )(s)
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses an expression of literals and names with the operators `+`, `-`, `*`, `/`,
+    /// `%` and `^`, from lowest to highest precedence, along with unary minus and
+    /// parentheses. Each binary operator is left associative apart from `^`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the next token of the remaining input, along with its offset into `s`
+        let token = |rest: &str| {
+            let rest = rest.trim_start();
+            let token = rest.split_whitespace().next()?;
+            Some((token.to_string(), s.len() - rest.len()))
+        };
+
+        match sum(s) {
+            Ok((rest, expr)) => match token(rest) {
+                None => Ok(expr),
+                Some((op, at)) => Err(ParseError::Invalid("operator", op).at(at)),
+            },
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => match (e.code, token(e.input)) {
+                (ErrorKind::Char, _) => Err(ParseError::Missing("`)`")),
+                (_, None) => Err(ParseError::Missing("operand")),
+                (_, Some((operand, at))) => Err(ParseError::Invalid("operand", operand).at(at)),
+            },
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::Missing("operand")),
+        }
+    }
+}
+
+/// The job of a monkey: its name and the expression it yells the value of
+#[derive(Debug, PartialEq, Clone)]
+pub struct Job {
+    pub name: String,
+    pub expr: Expr,
+}
+
+impl FromStr for Job {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (monkey, expr) = s.split_once(':').ok_or(ParseError::Missing("`:`"))?;
+        let monkey = monkey.trim();
+        if all_consuming(name)(monkey).is_err() {
+            let error = ParseError::Invalid("name", monkey.to_string());
+            return Err(error.at(parse::offset_of(s, monkey)));
+        }
+
+        let offset = parse::offset_of(s, expr);
+        Ok(Job {
+            name: monkey.to_string(),
+            expr: expr.parse().map_err(|e: ParseError| e.offset_by(offset))?,
+        })
+    }
+}
+
+/// Parses the job of each monkey along with the line it is defined on
+pub fn parse_monkeys(input: &str) -> Result<Vec<(usize, Job)>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line, text)| {
+            Job::from_str(text)
+                .map(|job| (line, job))
+                .map_err(|e| AocError::parse(line, text, e))
+        })
+        .collect()
+}
+
+impl Solution for Day21 {
+    type Input = Graph;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        let monkeys = parse_monkeys(input)?;
+
+        // without a root, only the issues that prevent evaluation are reported
+        let errors: Vec<_> = validate::validate(&monkeys, None)
+            .into_iter()
+            .map(|issue| format!("  {issue}"))
+            .collect();
+        if !errors.is_empty() {
+            return Err(AocError::solve(format!(
+                "Invalid monkey jobs:\n{}",
+                errors.join("\n")
+            )));
+        }
+
+        let vars = monkeys
+            .into_iter()
+            .map(|(_, job)| (job.name, job.expr))
+            .collect::<HashMap<String, Expr>>();
+
+        Graph::compile(&vars)
+    }
+
+    fn part1(graph: &Self::Input) -> Result<Self::Part1, AocError> {
+        graph.eval(ROOT)
+    }
+
+    fn part2(graph: &Self::Input) -> Result<Self::Part2, AocError> {
+        Solver::new(graph).balance(HUMN, ROOT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+    use symbolic::Equation;
+
+    fn num(i: i64) -> Box<Expr> {
+        Box::new(Expr::Num(i))
+    }
+
+    fn var(name: &str) -> Box<Expr> {
+        Box::new(Expr::Ref(Ref(name.to_string())))
+    }
+
+    #[test]
+    fn test_parse_expr_num() {
+        let job = Job::from_str("x: 5").unwrap();
+
+        assert_eq!(job.name, "x");
+        assert_eq!(job.expr, Expr::Num(5));
+    }
+
+    #[test]
+    fn test_parse_expr_add() {
+        let job = Job::from_str("x: a + b").unwrap();
+
+        assert_eq!(job.name, "x");
+        assert_eq!(job.expr, Expr::Op(Op::Add, var("a"), var("b")));
+    }
+
+    #[test]
+    fn test_parse_expr_sub() {
+        let job = Job::from_str("x: a - b").unwrap();
+
+        assert_eq!(job.name, "x");
+        assert_eq!(job.expr, Expr::Op(Op::Sub, var("a"), var("b")));
+    }
+
+    #[test]
+    fn test_parse_expr_mul() {
+        let job = Job::from_str("x: a * b").unwrap();
+
+        assert_eq!(job.name, "x");
+        assert_eq!(job.expr, Expr::Op(Op::Mul, var("a"), var("b")));
+    }
+
+    #[test]
+    fn test_parse_expr_div() {
+        let job = Job::from_str("x: a / b").unwrap();
+
+        assert_eq!(job.name, "x");
+        assert_eq!(job.expr, Expr::Op(Op::Div, var("a"), var("b")));
+    }
+
+    #[test]
+    fn test_parse_expr_mixed() {
+        let job = Job::from_str("x: -(a + 1) % 2").unwrap();
+        let sum = Box::new(Expr::Op(Op::Add, var("a"), num(1)));
+
+        assert_eq!(
+            job.expr,
+            Expr::Op(Op::Rem, Box::new(Expr::Neg(sum)), num(2))
+        );
+        assert_eq!(job.expr.refs(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_parse_expr_precedence() {
+        let parse = |s: &str| Expr::from_str(s).unwrap().to_string();
+
+        assert_eq!(parse("a + b * c - d"), "(a + (b * c)) - d");
+        assert_eq!(parse("a-b-c"), "(a - b) - c");
+        assert_eq!(parse("a / b % c * d"), "((a / b) % c) * d");
+        assert_eq!(parse("a ^ b ^ c"), "a ^ (b ^ c)");
+        assert_eq!(parse("-a ^ -2 * b"), "(-(a ^ (-2))) * b");
+        assert_eq!(parse("(a + b) * (c)"), "(a + b) * c");
+        assert_eq!(parse("a - -(-5)"), "a - 5");
+        assert_eq!(parse("(-5) ^ 2"), "(-5) ^ 2");
+    }
+
+    #[test]
+    fn test_parse_expr_invalid() {
+        let parse = |s: &str| Expr::from_str(s).unwrap_err();
+
+        assert_eq!(
+            parse("a &"),
+            ParseError::Invalid("operator", "&".to_string()).at(2)
+        );
+        assert_eq!(
+            parse("a b"),
+            ParseError::Invalid("operator", "b".to_string()).at(2)
+        );
+        assert_eq!(parse(" a +"), ParseError::Missing("operand"));
+        assert_eq!(parse(""), ParseError::Missing("operand"));
+        assert_eq!(parse("(a + b"), ParseError::Missing("`)`"));
+        assert_eq!(
+            parse("a + b)"),
+            ParseError::Invalid("operator", ")".to_string()).at(5)
+        );
+        assert_eq!(
+            parse("a * #"),
+            ParseError::Invalid("operand", "#".to_string()).at(4)
+        );
+        assert_eq!(
+            parse("99999999999999999999"),
+            ParseError::Invalid("operand", "99999999999999999999".to_string()).at(0)
+        );
+        assert_eq!(
+            Job::from_str("a b: 1").unwrap_err(),
+            ParseError::Invalid("name", "a b".to_string()).at(0)
+        );
+        // errors in the expression are located within the whole job
+        assert_eq!(
+            Job::from_str("ab: a + a a").unwrap_err(),
+            ParseError::Invalid("operator", "a".to_string()).at(10)
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        let vars = [
+            ("x".to_string(), Expr::Op(Op::Add, var("a"), var("b"))),
+            ("d".to_string(), Expr::Num(10)),
+            ("c".to_string(), Expr::Num(1)),
+            ("a".to_string(), Expr::Op(Op::Mul, var("c"), var("d"))),
+            ("b".to_string(), Expr::Num(5)),
+        ]
+        .into_iter()
+        .collect::<HashMap<String, Expr>>();
+
+        let graph = Graph::compile(&vars).unwrap();
+        let result = graph.eval("x").unwrap();
+
+        assert_eq!(result, 15);
+    }
+
+    #[test]
+    fn test_solve_not_integer() {
+        let graph = Day21::parse("root: a + b\na: humn * c\nb: 3\nc: 2\nhumn: 0\n").unwrap();
+        let err = Day21::part2(&graph).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "The value of humn is 3/2, which is not an integer within range"
+        );
+    }
+
+    const EXAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn test_example_equation() {
+        let graph = Day21::parse(EXAMPLE).unwrap();
+        let forms = symbolic::reduce(&graph, graph.id("humn").unwrap());
+        let equation = Equation {
+            unknown: "humn".to_string(),
+            lhs: forms[graph.id("pppw").unwrap()].clone().unwrap(),
+            rhs: forms[graph.id("sjmn").unwrap()].clone().unwrap(),
+        };
+
+        assert_eq!(equation.to_string(), "1/2*humn - 1/2 = 150");
+    }
+
+    #[test]
+    fn test_solve_both_sides() {
+        // the root compares humn * humn with 5 * humn - 6, despite subtracting them
+        let input = "\
+root: sqr - lin
+sqr: humn * humn
+lin: tmp - six
+tmp: five * humn
+five: 5
+six: 6
+humn: 0
+";
+        let graph = Day21::parse(input).unwrap();
+        let err = Day21::part2(&graph).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "humn^2 = 5*humn - 6 has 2 solutions: humn = 2, 3"
+        );
+
+        let graph =
+            Day21::parse("root: a * b\na: humn + one\nb: one + humn\none: 1\nhumn: 4\n").unwrap();
+        assert!(Day21::part2(&graph)
+            .unwrap_err()
+            .to_string()
+            .starts_with("Every value of humn"));
+
+        let graph =
+            Day21::parse("root: a / b\na: humn + one\nb: humn + humn\none: 1\nhumn: 4\n").unwrap();
+        assert_eq!(Day21::part2(&graph).unwrap(), 1);
+
+        let graph =
+            Day21::parse("root: a - b\na: humn + one\nb: humn - one\none: 1\nhumn: 4\n").unwrap();
+        assert!(Day21::part2(&graph)
+            .unwrap_err()
+            .to_string()
+            .starts_with("No value of humn"));
+    }
+
+    #[test]
+    fn test_solve_expressions() {
+        let graph = Day21::parse("root: 2 ^ -1 * humn + 1 - a % 4\na: 11\nhumn: 0\n").unwrap();
+        assert_eq!(Day21::part1(&graph).unwrap(), -2);
+        assert_eq!(Day21::part2(&graph).unwrap(), 4);
+
+        let graph = Day21::parse("root: (humn + 1) ^ 2 - 9\nhumn: 0\n").unwrap();
+        assert_eq!(
+            Day21::part2(&graph).unwrap_err().to_string(),
+            "humn^2 + 2*humn + 1 = 9 has 2 solutions: humn = -4, 2"
+        );
+
+        let graph = Day21::parse("root: a + 1\na: humn ^ 100000000\nhumn: 1\n").unwrap();
+        assert_eq!(
+            Day21::part2(&graph).unwrap_err().to_string(),
+            "Degree of the closed form too large in a"
+        );
+
+        let graph = Day21::parse("root: 2 ^ humn + 0\nhumn: 1\n").unwrap();
+        assert_eq!(
+            Day21::part2(&graph).unwrap_err().to_string(),
+            "No rational closed form in root"
+        );
+    }
+
+    #[test]
+    fn test_solve_large() {
+        let graph =
+            Day21::parse("root: a + b\na: humn * 1\nb: 1000000000000000077\nhumn: 0\n").unwrap();
+        assert_eq!(Day21::part2(&graph).unwrap(), 1_000_000_000_000_000_077);
+    }
+
+    #[test]
+    fn test_solve_undefined() {
+        // the value humn yells in the input is replaced, so it may divide by zero, and z
+        // is never used by root
+        let input = "root: a + b\na: one / humn\nb: 1\none: 1\nhumn: 0\nz: one / zero\nzero: 0\n";
+        let graph = Day21::parse(input).unwrap();
+        assert_eq!(
+            Day21::part1(&graph).unwrap_err().to_string(),
+            "Division by zero in a"
+        );
+        assert_eq!(Day21::part2(&graph).unwrap(), 1);
+
+        // but a monkey that is undefined whatever humn yells still is
+        let graph =
+            Day21::parse("root: a + humn\na: one % zero\none: 1\nzero: 0\nhumn: 0\n").unwrap();
+        assert_eq!(
+            Day21::part2(&graph).unwrap_err().to_string(),
+            "Division by zero in a"
+        );
+    }
+
+    /// Solves a chain of `n` monkeys in which every monkey depends on the next, so that
+    /// anything recursing per monkey overflows the 2 MiB stack of a test thread
+    fn check_deep_chain(n: usize) {
+        let mut input = format!(
+            "root: m0 + target\ntarget: {}\none: 1\nhumn: 3\n",
+            2 * n + 5
+        );
+        for i in 0..n - 1 {
+            input.push_str(&format!("m{i}: m{} + one\n", i + 1));
+        }
+        input.push_str(&format!("m{}: humn + one\n", n - 1));
+
+        let graph = Day21::parse(&input).unwrap();
+        let root = graph.id(ROOT).unwrap();
+        assert!(graph.dependents(graph.id(HUMN).unwrap()).contains(root));
+        assert_eq!(Day21::part1(&graph).unwrap(), 3 * n as i64 + 8);
+        assert_eq!(Day21::part2(&graph).unwrap(), n as i64 + 5);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        check_deep_chain(100_000);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        ignore = "takes about 25s without optimisations, run with `cargo test --release`"
+    )]
+    fn test_deep_chain() {
+        check_deep_chain(1_000_000);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let err = Day21::parse("root: a + b\na: b & c\nb: a - c\nc: 1\n").unwrap_err();
+        assert!(err.to_string().contains("Invalid operator"));
+
+        let err = Day21::parse("root: a + b\na: b * c\nb: a - c\nc: 1\nc: 2\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid monkey jobs:\n  Monkey c is defined on lines 4, 5\n  Cycle between monkeys a, b"
+        );
+    }
+
+    aoc_example!(Day21, EXAMPLE, part1 = 152, part2 = 301);
+}
@@ -0,0 +1,228 @@
+//! Questions about the monkeys' jobs beyond the two parts of the puzzle: the value of
+//! any monkey, or the value any monkey must yell for another to yell a given target.
+//! A [`Solver`] answers any number of queries against the same compiled graph.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::error::AocError;
+use aoc_common::parse::{self, ParseError};
+
+use super::graph::{self, Graph, Node, NodeId, Undefined};
+use super::symbolic::{self, Equation, RationalFn};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// The value the monkey yells
+    Eval(String),
+    /// The value `unknown` must yell for `target` to yell `value`
+    Solve {
+        unknown: String,
+        target: String,
+        value: i64,
+    },
+    /// The value `unknown` must yell for the two operands in the job of `root` to be
+    /// equal, as in part 2 of the puzzle
+    Balance { unknown: String, root: String },
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    /// Parses a query in one of the forms `eval <monkey>`, `solve <unknown>
+    /// <monkey>=<value>` or `balance <unknown> <root>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<_> = s.split_whitespace().collect();
+
+        match words[..] {
+            ["eval", monkey] => Ok(Self::Eval(monkey.to_string())),
+            ["solve", unknown, goal] => {
+                let (target, value) = goal.split_once('=').ok_or(ParseError::Missing("`=`"))?;
+                let value = value.parse().map_err(|_| {
+                    ParseError::Invalid("value", value.to_string()).at(parse::offset_of(s, value))
+                })?;
+
+                Ok(Self::Solve {
+                    unknown: unknown.to_string(),
+                    target: target.to_string(),
+                    value,
+                })
+            }
+            ["balance", unknown, root] => Ok(Self::Balance {
+                unknown: unknown.to_string(),
+                root: root.to_string(),
+            }),
+            [kind @ ("eval" | "solve" | "balance"), ..] => Err(ParseError::Syntax(format!(
+                "Wrong number of arguments for `{kind}`"
+            ))),
+            [kind, ..] => {
+                Err(ParseError::Invalid("query", kind.to_string()).at(parse::offset_of(s, kind)))
+            }
+            [] => Err(ParseError::Missing("query")),
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Eval(monkey) => write!(f, "eval {monkey}"),
+            Self::Solve {
+                unknown,
+                target,
+                value,
+            } => write!(f, "solve {unknown} {target}={value}"),
+            Self::Balance { unknown, root } => write!(f, "balance {unknown} {root}"),
+        }
+    }
+}
+
+/// Answers queries about a compiled graph, reducing it to closed forms at most once
+/// per unknown
+pub struct Solver<'a> {
+    graph: &'a Graph,
+    forms: HashMap<NodeId, Vec<Result<RationalFn, Undefined>>>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            forms: HashMap::new(),
+        }
+    }
+
+    /// The closed form of the node `id` in the unknown `x`
+    fn form(&mut self, x: NodeId, id: NodeId) -> Result<RationalFn, AocError> {
+        let graph = self.graph;
+        let forms = self
+            .forms
+            .entry(x)
+            .or_insert_with(|| symbolic::reduce(graph, x));
+
+        forms[id].clone().map_err(|e| graph.error(e))
+    }
+
+    /// Evaluates the monkey called `name`, failing unless its value is an integer
+    pub fn eval(&mut self, name: &str) -> Result<i64, AocError> {
+        self.graph.eval(name)
+    }
+
+    /// Finds the value `unknown` must yell for `target` to yell `value`
+    pub fn solve(&mut self, unknown: &str, target: &str, value: i64) -> Result<i64, AocError> {
+        let x = self.graph.require(unknown)?;
+        let target = self.graph.require(target)?;
+
+        let equation = Equation {
+            unknown: unknown.to_string(),
+            lhs: self.form(x, target)?,
+            rhs: RationalFn::constant(value.into()),
+        };
+        graph::integer(unknown, equation.solve()?)
+    }
+
+    /// The equation in `unknown` that holds when `root` sees its two operands as equal
+    pub fn equation(&mut self, unknown: &str, root: &str) -> Result<Equation, AocError> {
+        let x = self.graph.require(unknown)?;
+        let id = self.graph.require(root)?;
+
+        // whatever the job of root is, it actually compares the two monkeys for equality
+        let Node::Op(_, lhs, rhs) = *self.graph.node(id) else {
+            return Err(AocError::solve(format!(
+                "Expected {root} to compare two monkeys"
+            )));
+        };
+
+        Ok(Equation {
+            unknown: unknown.to_string(),
+            lhs: self.form(x, lhs)?,
+            rhs: self.form(x, rhs)?,
+        })
+    }
+
+    /// Finds the value `unknown` must yell for `root` to see its two operands as equal
+    pub fn balance(&mut self, unknown: &str, root: &str) -> Result<i64, AocError> {
+        let equation = self.equation(unknown, root)?;
+        graph::integer(unknown, equation.solve()?)
+    }
+
+    pub fn answer(&mut self, query: &Query) -> Result<i64, AocError> {
+        match query {
+            Query::Eval(monkey) => self.eval(monkey),
+            Query::Solve {
+                unknown,
+                target,
+                value,
+            } => self.solve(unknown, target, *value),
+            Query::Balance { unknown, root } => self.balance(unknown, root),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::d21::Day21;
+    use aoc_common::solution::Solution;
+
+    const INPUT: &str = "\
+root: a + b
+a: humn * 2
+b: c - humn
+c: 12
+humn: 1
+";
+
+    #[test]
+    fn test_parse_query() {
+        for s in ["eval root", "solve humn root=-21", "balance c root"] {
+            assert_eq!(s.parse::<Query>().unwrap().to_string(), s);
+        }
+
+        assert_eq!(
+            "solve humn root".parse::<Query>(),
+            Err(ParseError::Missing("`=`"))
+        );
+        assert_eq!(
+            "find root".parse::<Query>(),
+            Err(ParseError::Invalid("query", "find".to_string()).at(0))
+        );
+        assert!("eval".parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn test_answer() {
+        let graph = Day21::parse(INPUT).unwrap();
+        let mut solver = Solver::new(&graph);
+        let mut answer = |s: &str| solver.answer(&s.parse().unwrap());
+
+        assert_eq!(answer("eval root").unwrap(), 13);
+        assert_eq!(answer("eval b").unwrap(), 11);
+        assert_eq!(answer("balance humn root").unwrap(), 4);
+        assert_eq!(answer("solve humn root=21").unwrap(), 9);
+        assert_eq!(answer("solve humn b=0").unwrap(), 12);
+        assert_eq!(answer("solve c root=20").unwrap(), 19);
+        assert_eq!(answer("balance c root").unwrap(), 3);
+
+        assert_eq!(
+            answer("balance humn c").unwrap_err().to_string(),
+            "Expected c to compare two monkeys"
+        );
+        assert_eq!(
+            answer("solve c a=3").unwrap_err().to_string(),
+            "No value of c solves 2 = 3"
+        );
+        assert!(answer("eval x").is_err());
+    }
+
+    #[test]
+    fn test_equation() {
+        let graph = Day21::parse(INPUT).unwrap();
+        let mut solver = Solver::new(&graph);
+
+        let equation = solver.equation("humn", "root").unwrap();
+        assert_eq!(equation.to_string(), "2*humn = -humn + 12");
+        assert!(solver.equation("humn", "c").is_err());
+    }
+}
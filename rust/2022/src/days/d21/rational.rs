@@ -0,0 +1,256 @@
+//! Exact rational numbers with checked `i128` arithmetic, so that no division ever
+//! truncates and no overflow goes unnoticed.
+
+use std::fmt;
+
+/// The reason an arithmetic operation has no result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    Overflow,
+    DivisionByZero,
+    /// A power was taken with an exponent that is not a whole number
+    FractionalExponent,
+    /// An operation involving the unknown has no closed form as a rational function
+    NotRational,
+    /// A closed form would be a polynomial of too high a degree to work with
+    DegreeTooLarge,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "Overflow"),
+            Self::DivisionByZero => write!(f, "Division by zero"),
+            Self::FractionalExponent => write!(f, "Fractional exponent"),
+            Self::NotRational => write!(f, "No rational closed form"),
+            Self::DegreeTooLarge => write!(f, "Degree of the closed form too large"),
+        }
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// A fraction in lowest terms, whose denominator is always positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// Creates the fraction `num / den`, reduced to lowest terms
+    pub fn new(num: i128, den: i128) -> Result<Self, ArithmeticError> {
+        if den == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let g = gcd(num, den);
+        let (num, den) = (num / g, den / g);
+        if den < 0 {
+            let num = num.checked_neg().ok_or(ArithmeticError::Overflow)?;
+            let den = den.checked_neg().ok_or(ArithmeticError::Overflow)?;
+            return Ok(Self { num, den });
+        }
+
+        Ok(Self { num, den })
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    pub fn is_one(&self) -> bool {
+        *self == Self::ONE
+    }
+
+    /// Returns the value as an `i64`, if it is a whole number within range
+    pub fn to_integer(&self) -> Option<i64> {
+        (self.den == 1).then(|| i64::try_from(self.num).ok())?
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        // working over the lcm of the denominators keeps intermediate values small
+        let g = gcd(self.den, rhs.den);
+        let (l, r) = (rhs.den / g, self.den / g);
+        let num = mul(self.num, l)?
+            .checked_add(mul(rhs.num, r)?)
+            .ok_or(ArithmeticError::Overflow)?;
+        Self::new(num, mul(self.den, l)?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        // cross-cancelling first avoids overflowing on products that reduce
+        let g1 = gcd(self.num, rhs.den).max(1);
+        let g2 = gcd(rhs.num, self.den).max(1);
+        Self::new(
+            mul(self.num / g1, rhs.num / g2)?,
+            mul(self.den / g2, rhs.den / g1)?,
+        )
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        self.checked_mul(rhs.recip()?)
+    }
+
+    pub fn checked_neg(self) -> Result<Self, ArithmeticError> {
+        let num = self.num.checked_neg().ok_or(ArithmeticError::Overflow)?;
+        Ok(Self { num, den: self.den })
+    }
+
+    pub fn checked_abs(self) -> Result<Self, ArithmeticError> {
+        if self.is_negative() {
+            self.checked_neg()
+        } else {
+            Ok(self)
+        }
+    }
+
+    pub fn recip(self) -> Result<Self, ArithmeticError> {
+        Self::new(self.den, self.num)
+    }
+
+    /// The remainder of dividing by `rhs`, whose sign matches the sign of `self` just as
+    /// with `%` on integers, i.e. `self - rhs * q` where `q` is the quotient truncated
+    /// towards zero
+    pub fn checked_rem(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        let quotient = self.checked_div(rhs)?;
+        let truncated = Self::new(quotient.num / quotient.den, 1)?;
+        self.checked_sub(rhs.checked_mul(truncated)?)
+    }
+
+    /// Raises this to the power `exp`, which must be a whole number
+    pub fn checked_pow(self, exp: Self) -> Result<Self, ArithmeticError> {
+        if exp.den != 1 {
+            return Err(ArithmeticError::FractionalExponent);
+        }
+
+        let base = if exp.is_negative() {
+            self.recip()?
+        } else {
+            self
+        };
+        let (mut base, mut exp, mut result) = (base, exp.num.unsigned_abs(), Self::ONE);
+        // exponentiation by squaring, so that even huge exponents of 0, 1 or -1 are quick
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn mul(a: i128, b: i128) -> Result<i128, ArithmeticError> {
+    a.checked_mul(b).ok_or(ArithmeticError::Overflow)
+}
+
+impl From<i64> for Rational {
+    fn from(i: i64) -> Self {
+        Self {
+            num: i.into(),
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{den}", self.num),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(1, 2).checked_add(r(1, 3)), Ok(r(5, 6)));
+        assert_eq!(r(1, 2).checked_sub(r(3, 4)), Ok(r(-1, 4)));
+        assert_eq!(r(2, 3).checked_mul(r(9, 4)), Ok(r(3, 2)));
+        assert_eq!(r(7, 1).checked_div(r(2, 1)).unwrap().to_string(), "7/2");
+        assert_eq!(r(8, 2).to_integer(), Some(4));
+        assert_eq!(r(7, 2).to_integer(), None);
+    }
+
+    #[test]
+    fn test_rem_pow() {
+        assert_eq!(r(7, 1).checked_rem(r(3, 1)), Ok(r(1, 1)));
+        assert_eq!(r(-7, 1).checked_rem(r(3, 1)), Ok(r(-1, 1)));
+        assert_eq!(r(7, 2).checked_rem(r(1, 1)), Ok(r(1, 2)));
+        assert_eq!(r(2, 3).checked_pow(r(3, 1)), Ok(r(8, 27)));
+        assert_eq!(r(2, 1).checked_pow(r(-2, 1)), Ok(r(1, 4)));
+        assert_eq!(
+            Rational::ZERO.checked_pow(Rational::ZERO),
+            Ok(Rational::ONE)
+        );
+        assert_eq!(r(-1, 1).checked_pow(r(i128::MAX, 1)), Ok(r(-1, 1)));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Rational::new(1, 0), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(
+            Rational::ONE.checked_div(Rational::ZERO),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            r(i128::MAX, 1).checked_add(Rational::ONE),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(r(i128::MAX, 1).to_integer(), None);
+        assert_eq!(
+            r(1, 1).checked_rem(Rational::ZERO),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            r(4, 1).checked_pow(r(1, 2)),
+            Err(ArithmeticError::FractionalExponent)
+        );
+        assert_eq!(
+            Rational::ZERO.checked_pow(r(-1, 1)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            r(2, 1).checked_pow(r(200, 1)),
+            Err(ArithmeticError::Overflow)
+        );
+    }
+}
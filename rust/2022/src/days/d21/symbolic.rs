@@ -0,0 +1,629 @@
+//! Symbolic evaluation of the graph in terms of a single unknown. Every node that
+//! depends on the unknown reduces to a closed form: a polynomial `a*x + b` in the
+//! common case, a higher degree polynomial when the unknown is reached through several
+//! paths, or a quotient of polynomials when the unknown ends up in a divisor.
+
+use std::fmt;
+
+use aoc_common::error::AocError;
+
+use super::graph::{Graph, Node, NodeId, Op, Undefined};
+use super::rational::{ArithmeticError, Rational};
+
+/// The highest degree of a polynomial in a closed form, beyond which finding roots is
+/// hopeless anyway
+const MAX_DEGREE: usize = 64;
+
+/// A polynomial in the unknown with exact coefficients, stored from the constant term
+/// upwards without any trailing zero coefficients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Poly(Vec<Rational>);
+
+impl Poly {
+    pub fn new(mut coefficients: Vec<Rational>) -> Self {
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+        Self(coefficients)
+    }
+
+    pub fn constant(c: Rational) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// The polynomial `x`
+    pub fn x() -> Self {
+        Self(vec![Rational::ZERO, Rational::ONE])
+    }
+
+    /// The degree of the polynomial, or `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.0.len().checked_sub(1)
+    }
+
+    /// The coefficient of `x^i`
+    pub fn coefficient(&self, i: usize) -> Rational {
+        self.0.get(i).copied().unwrap_or(Rational::ZERO)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        let len = self.0.len().max(rhs.0.len());
+        (0..len)
+            .map(|i| self.coefficient(i).checked_add(rhs.coefficient(i)))
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        self.checked_add(&rhs.checked_scale(Rational::ONE.checked_neg()?)?)
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if self.is_zero() || rhs.is_zero() {
+            return Ok(Self(vec![]));
+        }
+        if self.0.len() + rhs.0.len() - 2 > MAX_DEGREE {
+            return Err(ArithmeticError::DegreeTooLarge);
+        }
+
+        let mut product = vec![Rational::ZERO; self.0.len() + rhs.0.len() - 1];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in rhs.0.iter().enumerate() {
+                product[i + j] = product[i + j].checked_add(a.checked_mul(*b)?)?;
+            }
+        }
+
+        Ok(Self::new(product))
+    }
+
+    /// Multiplies every coefficient by `c`
+    pub fn checked_scale(&self, c: Rational) -> Result<Self, ArithmeticError> {
+        self.0
+            .iter()
+            .map(|a| a.checked_mul(c))
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    /// Evaluates the polynomial at `x`
+    pub fn eval(&self, x: Rational) -> Result<Rational, ArithmeticError> {
+        self.0
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+    }
+
+    /// Finds every rational root of the polynomial, in ascending order. A linear
+    /// polynomial is solved exactly. Otherwise each real root is first located
+    /// numerically, then candidate fractions close to it are checked exactly, so
+    /// irrational roots are never reported. The zero polynomial has no roots listed, as
+    /// every value is a root.
+    pub fn roots(&self) -> Result<Vec<Rational>, ArithmeticError> {
+        let degree = match self.degree() {
+            None | Some(0) => return Ok(vec![]),
+            // solved exactly, since floats cannot tell apart integers beyond 2^53
+            Some(1) => return Ok(vec![self.0[0].checked_neg()?.checked_div(self.0[1])?]),
+            Some(degree) => degree,
+        };
+
+        // by the rational root theorem, every denominator divides the leading
+        // coefficient once all of the coefficients are scaled to integers
+        let scale = self.0.iter().try_fold(1, |l, c| lcm(l, c.denom()))?;
+        let leading = self
+            .coefficient(degree)
+            .checked_mul(Rational::new(scale, 1)?)?;
+        let denominators = divisors(leading.numer());
+
+        let coefficients: Vec<f64> = self.0.iter().map(|c| to_f64(*c)).collect();
+        let mut roots = vec![];
+        for x in real_roots(&coefficients) {
+            for &q in &denominators {
+                let p = (x * q as f64).round() as i128;
+                // the approximation may be off by one after rounding
+                for p in [p.saturating_sub(1), p, p.saturating_add(1)] {
+                    let candidate = Rational::new(p, q)?;
+                    // a candidate too large to evaluate exactly cannot be confirmed
+                    let root = self.eval(candidate).is_ok_and(|y| y.is_zero());
+                    if root && !roots.contains(&candidate) {
+                        roots.push(candidate);
+                    }
+                }
+            }
+        }
+
+        roots.sort_by(|a, b| to_f64(*a).total_cmp(&to_f64(*b)));
+        Ok(roots)
+    }
+
+    /// Formats the polynomial with the unknown called `var`, highest degree first
+    pub fn display<'a>(&'a self, var: &'a str) -> impl fmt::Display + 'a {
+        Display(move |f: &mut fmt::Formatter| {
+            let terms: Vec<_> = self
+                .0
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, c)| !c.is_zero())
+                .collect();
+            if terms.is_empty() {
+                return write!(f, "0");
+            }
+
+            for (n, &(i, c)) in terms.iter().enumerate() {
+                let sign = match (n, c.is_negative()) {
+                    (0, true) => "-",
+                    (0, false) => "",
+                    (_, true) => " - ",
+                    (_, false) => " + ",
+                };
+                let c = c.checked_abs().map_err(|_| fmt::Error)?;
+                let power = match i {
+                    1 => var.to_string(),
+                    _ => format!("{var}^{i}"),
+                };
+
+                match (i, c.is_one()) {
+                    (0, _) => write!(f, "{sign}{c}")?,
+                    (_, true) => write!(f, "{sign}{power}")?,
+                    (_, false) => write!(f, "{sign}{c}*{power}")?,
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn to_f64(r: Rational) -> f64 {
+    r.numer() as f64 / r.denom() as f64
+}
+
+fn lcm(a: i128, b: i128) -> Result<i128, ArithmeticError> {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    (a / x.abs())
+        .checked_mul(b)
+        .map(i128::abs)
+        .ok_or(ArithmeticError::Overflow)
+}
+
+/// The positive divisors of `n`. Only `1` is returned for numbers too large to be
+/// factorised by trial division, which limits the roots found to integers.
+fn divisors(n: i128) -> Vec<i128> {
+    const LIMIT: i128 = 1_000_000;
+
+    let n = n.abs();
+    let mut divisors = vec![];
+    let mut i = 1;
+    while i * i <= n && i <= LIMIT {
+        if n % i == 0 {
+            divisors.push(i);
+            divisors.push(n / i);
+        }
+        i += 1;
+    }
+
+    if i <= n / i {
+        return vec![1];
+    }
+    divisors.sort();
+    divisors.dedup();
+    divisors
+}
+
+fn eval_f64(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// Locates the real roots of a polynomial approximately. The roots of its derivative
+/// split the line into intervals on which it is monotonic, each of which holds at
+/// most one root that is found by bisection. The turning points are included as
+/// well, as they may be roots of even multiplicity.
+fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    match coefficients {
+        [] | [_] => return vec![],
+        [b, a] => return vec![-b / a],
+        _ => (),
+    }
+
+    let n = coefficients.len() - 1;
+    let leading = coefficients[n];
+    // every root lies within Cauchy's bound
+    let bound = 1.0
+        + coefficients[..n]
+            .iter()
+            .map(|c| (c / leading).abs())
+            .fold(0.0, f64::max);
+
+    let derivative: Vec<f64> = (1..=n).map(|i| coefficients[i] * i as f64).collect();
+    let mut turning: Vec<f64> = real_roots(&derivative)
+        .into_iter()
+        .filter(|x| x.abs() < bound)
+        .collect();
+    turning.sort_by(f64::total_cmp);
+
+    let mut points = vec![-bound];
+    points.extend(&turning);
+    points.push(bound);
+
+    let mut roots = turning;
+    for pair in points.windows(2) {
+        let (mut lo, mut hi) = (pair[0], pair[1]);
+        let rising = eval_f64(coefficients, lo) < eval_f64(coefficients, hi);
+        if eval_f64(coefficients, lo).signum() == eval_f64(coefficients, hi).signum() {
+            continue;
+        }
+
+        for _ in 0..200 {
+            let mid = lo + (hi - lo) / 2.0;
+            if (eval_f64(coefficients, mid) < 0.0) == rising {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        roots.push(lo);
+    }
+
+    roots
+}
+
+/// Adapts a formatting closure into a value implementing `Display`
+struct Display<F>(F);
+
+impl<F: Fn(&mut fmt::Formatter) -> fmt::Result> fmt::Display for Display<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+impl fmt::Display for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display("x"))
+    }
+}
+
+/// A quotient of two polynomials. Constant denominators are always divided out, and
+/// other denominators are kept monic, so a linear form is always stored as `(a*x + b) / 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RationalFn {
+    pub num: Poly,
+    pub den: Poly,
+}
+
+impl RationalFn {
+    pub fn new(num: Poly, den: Poly) -> Result<Self, ArithmeticError> {
+        let Some(degree) = den.degree() else {
+            return Err(ArithmeticError::DivisionByZero);
+        };
+
+        let leading = den.coefficient(degree).recip()?;
+        Ok(Self {
+            num: num.checked_scale(leading)?,
+            den: den.checked_scale(leading)?,
+        })
+    }
+
+    pub fn constant(c: Rational) -> Self {
+        Self {
+            num: Poly::constant(c),
+            den: Poly::constant(Rational::ONE),
+        }
+    }
+
+    /// The unknown itself
+    pub fn x() -> Self {
+        Self {
+            num: Poly::x(),
+            den: Poly::constant(Rational::ONE),
+        }
+    }
+
+    /// Returns the polynomial this is equal to, if the denominator is constant
+    pub fn as_poly(&self) -> Option<&Poly> {
+        (self.den.degree() == Some(0)).then_some(&self.num)
+    }
+
+    pub fn apply(&self, op: Op, rhs: &Self) -> Result<Self, ArithmeticError> {
+        let (a, b, c, d) = (&self.num, &self.den, &rhs.num, &rhs.den);
+
+        match op {
+            // a/b + c/d = (ad + cb) / bd, reusing the denominator when they are equal
+            Op::Add | Op::Sub if b == d => {
+                let num = match op {
+                    Op::Add => a.checked_add(c)?,
+                    _ => a.checked_sub(c)?,
+                };
+                Self::new(num, b.clone())
+            }
+            Op::Add => Self::new(
+                a.checked_mul(d)?.checked_add(&c.checked_mul(b)?)?,
+                b.checked_mul(d)?,
+            ),
+            Op::Sub => Self::new(
+                a.checked_mul(d)?.checked_sub(&c.checked_mul(b)?)?,
+                b.checked_mul(d)?,
+            ),
+            Op::Mul => Self::new(a.checked_mul(c)?, b.checked_mul(d)?),
+            Op::Div => Self::new(a.checked_mul(d)?, b.checked_mul(c)?),
+            // a remainder is only rational when neither side depends on the unknown
+            Op::Rem => match (self.as_constant(), rhs.as_constant()) {
+                (Some(a), Some(b)) => Ok(Self::constant(a.checked_rem(b)?)),
+                _ => Err(ArithmeticError::NotRational),
+            },
+            Op::Pow => match rhs.as_constant() {
+                Some(exp) => self.checked_pow(exp),
+                None => Err(ArithmeticError::NotRational),
+            },
+        }
+    }
+
+    /// Returns the constant this is equal to, if it does not depend on the unknown
+    pub fn as_constant(&self) -> Option<Rational> {
+        let num = self.as_poly()?;
+        (num.degree().unwrap_or(0) == 0).then(|| num.coefficient(0))
+    }
+
+    /// Raises this to the power `exp`, which must be a whole number. Squaring the base
+    /// soon exceeds [`MAX_DEGREE`] for a large exponent, unless the base is constant.
+    fn checked_pow(&self, exp: Rational) -> Result<Self, ArithmeticError> {
+        let exp = exp
+            .to_integer()
+            .ok_or(ArithmeticError::FractionalExponent)?;
+        let mut base = if exp < 0 {
+            Self::new(self.den.clone(), self.num.clone())?
+        } else {
+            self.clone()
+        };
+
+        let (mut exp, mut result) = (exp.unsigned_abs(), Self::constant(Rational::ONE));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.apply(Op::Mul, &base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.apply(Op::Mul, &base)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Formats the quotient with the unknown called `var`
+    pub fn display<'a>(&'a self, var: &'a str) -> impl fmt::Display + 'a {
+        Display(move |f: &mut fmt::Formatter| {
+            let group = |p: &Poly| p.0.iter().filter(|c| !c.is_zero()).count() > 1;
+
+            if self.as_poly().is_some() {
+                return write!(f, "{}", self.num.display(var));
+            }
+            if group(&self.num) {
+                write!(f, "({})", self.num.display(var))?;
+            } else {
+                write!(f, "{}", self.num.display(var))?;
+            }
+            // the denominator is monic, so a single term is never a product
+            if group(&self.den) {
+                write!(f, " / ({})", self.den.display(var))
+            } else {
+                write!(f, " / {}", self.den.display(var))
+            }
+        })
+    }
+}
+
+/// An equation between two closed forms in the unknown called `unknown`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub unknown: String,
+    pub lhs: RationalFn,
+    pub rhs: RationalFn,
+}
+
+impl Equation {
+    /// Moves every term to one side, returning the polynomial whose roots solve the
+    /// equation. Roots that are also roots of either denominator must be excluded.
+    pub fn polynomial(&self) -> Result<Poly, ArithmeticError> {
+        let lhs = self.lhs.num.checked_mul(&self.rhs.den)?;
+        let rhs = self.rhs.num.checked_mul(&self.lhs.den)?;
+        lhs.checked_sub(&rhs)
+    }
+
+    /// Finds every solution of the equation. Values at which either side is undefined,
+    /// because its denominator is zero, are never solutions.
+    pub fn solutions(&self) -> Result<Solutions, AocError> {
+        let arithmetic = |e| AocError::solve(format!("{e} while solving {self}"));
+        let p = self.polynomial().map_err(arithmetic)?;
+        if p.is_zero() {
+            return Ok(Solutions::All);
+        }
+
+        let mut roots = vec![];
+        for x in p.roots().map_err(arithmetic)? {
+            let mut defined = true;
+            for den in [&self.lhs.den, &self.rhs.den] {
+                defined &= !den.eval(x).map_err(arithmetic)?.is_zero();
+            }
+            if defined {
+                roots.push(x);
+            }
+        }
+
+        if roots.is_empty() {
+            Ok(Solutions::Empty)
+        } else {
+            Ok(Solutions::Finite(roots))
+        }
+    }
+
+    /// Solves the equation, failing unless it has exactly one solution
+    pub fn solve(&self) -> Result<Rational, AocError> {
+        match self.solutions()? {
+            Solutions::Finite(roots) if roots.len() == 1 => Ok(roots[0]),
+            Solutions::Finite(roots) => {
+                let roots: Vec<_> = roots.iter().map(|x| x.to_string()).collect();
+                Err(AocError::solve(format!(
+                    "{self} has {} solutions: {} = {}",
+                    roots.len(),
+                    self.unknown,
+                    roots.join(", ")
+                )))
+            }
+            Solutions::Empty => Err(AocError::solve(format!(
+                "No value of {} solves {self}",
+                self.unknown
+            ))),
+            Solutions::All => Err(AocError::solve(format!(
+                "Every value of {} solves {self}",
+                self.unknown
+            ))),
+        }
+    }
+}
+
+/// The values of the unknown that solve an [`Equation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    /// No (rational) value solves the equation
+    Empty,
+    /// Every value at which the equation is defined solves it
+    All,
+    /// Exactly these values solve the equation, in ascending order
+    Finite(Vec<Rational>),
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {}",
+            self.lhs.display(&self.unknown),
+            self.rhs.display(&self.unknown)
+        )
+    }
+}
+
+/// Reduces every node of the graph to a closed form in the node `unknown`, which is
+/// treated as a variable rather than by its value. Nodes that do not depend on the
+/// unknown are reduced to constants, so only the nodes that fail to evaluate or reduce,
+/// and those depending on them, are left undefined.
+pub fn reduce(graph: &Graph, unknown: NodeId) -> Vec<Result<RationalFn, Undefined>> {
+    let dependents = graph.dependents(unknown);
+    let mut forms = vec![Ok(RationalFn::x()); graph.len()];
+
+    for &id in graph.order() {
+        let undefined = |e| Undefined { error: e, at: id };
+        forms[id] = if id == unknown {
+            Ok(RationalFn::x())
+        } else if !dependents.contains(id) {
+            graph.result(id).map(RationalFn::constant)
+        } else {
+            match *graph.node(id) {
+                Node::Num(i) => Ok(RationalFn::constant(i.into())),
+                Node::Ref(other) => forms[other].clone(),
+                Node::Neg(operand) => forms[operand].as_ref().map_err(|e| *e).and_then(|f| {
+                    RationalFn::constant(Rational::ZERO)
+                        .apply(Op::Sub, f)
+                        .map_err(undefined)
+                }),
+                Node::Op(op, lhs, rhs) => match (&forms[lhs], &forms[rhs]) {
+                    (Ok(l), Ok(r)) => l.apply(op, r).map_err(undefined),
+                    (Err(e), _) | (_, Err(e)) => Err(*e),
+                },
+            }
+        };
+    }
+
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(i: i64) -> Rational {
+        i.into()
+    }
+
+    #[test]
+    fn test_poly_display() {
+        let p = Poly::new(vec![r(-5), r(1), Rational::new(3, 2).unwrap()]);
+        assert_eq!(p.display("humn").to_string(), "3/2*humn^2 + humn - 5");
+        assert_eq!(Poly::new(vec![r(0), r(-2)]).to_string(), "-2*x");
+        assert_eq!(Poly::new(vec![r(0)]).to_string(), "0");
+    }
+
+    #[test]
+    fn test_roots() {
+        // 2x^3 - 3x^2 + x = x(2x - 1)(x - 1)
+        let p = Poly::new(vec![r(0), r(1), r(-3), r(2)]);
+        let half = Rational::new(1, 2).unwrap();
+        assert_eq!(p.roots().unwrap(), vec![r(0), half, r(1)]);
+
+        // (x - 4)^2 only touches zero
+        let p = Poly::new(vec![r(16), r(-8), r(1)]);
+        assert_eq!(p.roots().unwrap(), vec![r(4)]);
+
+        // a root beyond 2^53, where floats can no longer represent every integer
+        let p = Poly::new(vec![r(-1_000_000_000_000_000_077), r(1)]);
+        assert_eq!(p.roots().unwrap(), vec![r(1_000_000_000_000_000_077)]);
+        let p = Poly::new(vec![r(1_000_000_000_000_000_077), r(-3)]);
+        assert_eq!(
+            p.roots().unwrap(),
+            vec![Rational::new(1_000_000_000_000_000_077, 3).unwrap()]
+        );
+        assert!(Poly::constant(r(5)).roots().unwrap().is_empty());
+
+        // x^2 - 2 has no rational roots
+        assert!(Poly::new(vec![r(-2), r(0), r(1)])
+            .roots()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_linear() {
+        // (x + 3) * 2 / 4 = 5
+        let lhs = RationalFn::x()
+            .apply(Op::Add, &RationalFn::constant(r(3)))
+            .and_then(|f| f.apply(Op::Mul, &RationalFn::constant(r(2))))
+            .and_then(|f| f.apply(Op::Div, &RationalFn::constant(r(4))))
+            .unwrap();
+        let equation = Equation {
+            unknown: "x".to_string(),
+            lhs,
+            rhs: RationalFn::constant(r(5)),
+        };
+
+        assert_eq!(equation.to_string(), "1/2*x + 3/2 = 5");
+        assert_eq!(equation.solve().unwrap(), r(7));
+    }
+
+    #[test]
+    fn test_rational_function() {
+        // 6 / (x - 1) = 3
+        let lhs = RationalFn::constant(r(6))
+            .apply(
+                Op::Div,
+                &RationalFn::x()
+                    .apply(Op::Sub, &RationalFn::constant(r(1)))
+                    .unwrap(),
+            )
+            .unwrap();
+        let equation = Equation {
+            unknown: "x".to_string(),
+            lhs,
+            rhs: RationalFn::constant(r(3)),
+        };
+
+        assert_eq!(equation.to_string(), "6 / (x - 1) = 3");
+        assert_eq!(equation.solve().unwrap(), r(3));
+    }
+}
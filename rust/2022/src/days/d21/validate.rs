@@ -0,0 +1,257 @@
+//! Checks the parsed jobs of the monkeys before anything is evaluated, reporting every
+//! problem at once rather than stopping at the first.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Job;
+
+/// A problem with the jobs of the monkeys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The monkeys, in order of name, whose jobs depend on each other in a cycle
+    Cycle(Vec<String>),
+    /// The job of `monkey` refers to a monkey that is never defined
+    Dangling { monkey: String, name: String },
+    /// The monkey is defined on each of the given lines
+    Duplicate { name: String, lines: Vec<usize> },
+    /// The monkey is never used, directly or indirectly, by the root monkey
+    Unreachable { name: String, root: String },
+}
+
+impl Issue {
+    /// Returns `true` if the jobs cannot be evaluated because of this issue. Unreachable
+    /// monkeys are only worth a warning.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Self::Unreachable { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Cycle(names) => write!(f, "Cycle between monkeys {}", names.join(", ")),
+            Self::Dangling { monkey, name } => {
+                write!(f, "Monkey {monkey} refers to undeclared monkey {name}")
+            }
+            Self::Duplicate { name, lines } => {
+                let lines: Vec<_> = lines.iter().map(|l| l.to_string()).collect();
+                write!(f, "Monkey {name} is defined on lines {}", lines.join(", "))
+            }
+            Self::Unreachable { name, root } => {
+                write!(f, "Monkey {name} is not reachable from {root}")
+            }
+        }
+    }
+}
+
+/// Finds every strongly connected component of the graph with more than one node, or
+/// with a node that refers to itself, using an iterative version of Tarjan's algorithm
+fn cycles(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let n = edges.len();
+    let (mut index, mut low) = (vec![UNVISITED; n], vec![0; n]);
+    let mut on_stack = vec![false; n];
+    let (mut stack, mut components) = (vec![], vec![]);
+    let mut next = 0;
+
+    for start in 0..n {
+        if index[start] != UNVISITED {
+            continue;
+        }
+
+        // each frame holds a node and the position of the next edge to follow
+        let mut frames = vec![(start, 0)];
+        index[start] = next;
+        low[start] = next;
+        next += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some(frame) = frames.last_mut() {
+            let v = frame.0;
+            if let Some(&w) = edges[v].get(frame.1) {
+                frame.1 += 1;
+                if index[w] == UNVISITED {
+                    index[w] = next;
+                    low[w] = next;
+                    next += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                let mut component = vec![];
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 || edges[v].contains(&v) {
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Validates the jobs of the monkeys, each given with the line it was defined on,
+/// reporting cycles, references to undeclared monkeys, monkeys defined more than once
+/// and, when a `root` is given that exists, monkeys that the root never uses
+pub fn validate(monkeys: &[(usize, Job)], root: Option<&str>) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    // only the first definition of each monkey is followed
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut lines: Vec<Vec<usize>> = vec![];
+    let mut defined = vec![];
+    for (line, job) in monkeys {
+        match ids.get(job.name.as_str()) {
+            Some(&id) => lines[id].push(*line),
+            None => {
+                ids.insert(&job.name, defined.len());
+                lines.push(vec![*line]);
+                defined.push(job);
+            }
+        }
+    }
+
+    for (job, lines) in defined.iter().zip(&lines) {
+        if lines.len() > 1 {
+            issues.push(Issue::Duplicate {
+                name: job.name.clone(),
+                lines: lines.clone(),
+            });
+        }
+    }
+
+    let mut edges = vec![vec![]; defined.len()];
+    for (id, job) in defined.iter().enumerate() {
+        for name in job.expr.refs() {
+            match ids.get(name) {
+                Some(&child) => edges[id].push(child),
+                None => issues.push(Issue::Dangling {
+                    monkey: job.name.clone(),
+                    name: name.to_string(),
+                }),
+            }
+        }
+    }
+
+    let mut cycles: Vec<Vec<String>> = cycles(&edges)
+        .into_iter()
+        .map(|component| {
+            let mut names: Vec<_> = component
+                .into_iter()
+                .map(|id| defined[id].name.clone())
+                .collect();
+            names.sort();
+            names
+        })
+        .collect();
+    cycles.sort();
+    issues.extend(cycles.into_iter().map(Issue::Cycle));
+
+    if let Some((root, &start)) = root.and_then(|root| Some((root, ids.get(root)?))) {
+        let mut reachable = vec![false; defined.len()];
+        let mut stack = vec![start];
+        reachable[start] = true;
+        while let Some(id) = stack.pop() {
+            for &child in &edges[id] {
+                if !reachable[child] {
+                    reachable[child] = true;
+                    stack.push(child);
+                }
+            }
+        }
+
+        issues.extend((0..defined.len()).filter(|&id| !reachable[id]).map(|id| {
+            Issue::Unreachable {
+                name: defined[id].name.clone(),
+                root: root.to_string(),
+            }
+        }));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn validate_lines(lines: &[&str]) -> Vec<String> {
+        let monkeys: Vec<_> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (i + 1, Job::from_str(line).unwrap()))
+            .collect();
+        validate(&monkeys, Some("root"))
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(validate_lines(&["root: a + b", "a: 1", "b: a * a"]).is_empty());
+    }
+
+    #[test]
+    fn test_every_issue() {
+        let issues = validate_lines(&[
+            "root: a + b",
+            "a: b - c",
+            "b: a * z",
+            "c: c / c",
+            "a: 4",
+            "d: 5",
+        ]);
+
+        assert_eq!(
+            issues,
+            vec![
+                "Monkey a is defined on lines 2, 5",
+                "Monkey b refers to undeclared monkey z",
+                "Cycle between monkeys a, b",
+                "Cycle between monkeys c",
+                "Monkey d is not reachable from root",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_without_root() {
+        // unreachable monkeys are only reported relative to a root that exists
+        let monkeys: Vec<_> = ["a: 1", "b: 2"]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (i + 1, Job::from_str(line).unwrap()))
+            .collect();
+
+        assert_eq!(validate(&monkeys, None), vec![]);
+        assert_eq!(validate(&monkeys, Some("c")), vec![]);
+        assert_eq!(
+            validate(&monkeys, Some("a")),
+            vec![Issue::Unreachable {
+                name: "b".to_string(),
+                root: "a".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::error::AocError;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::point::{Bounds, Point};
+use aoc_common::solution::Solution;
+
+pub struct Day23;
+
+pub type OccupiedPoints = HashSet<Point>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProposedMove {
+    prev: Point,
+    next: Point,
+}
+
+impl ProposedMove {
+    fn accept(&self) -> Point {
+        self.next
+    }
+
+    fn reject(&self) -> Point {
+        self.prev
+    }
+}
+
+/// Proposes moving the elf at point `p` to a new point by inspecting adjacent points in the correct order
+fn propose_move(
+    p: &Point,
+    occupied: &OccupiedPoints,
+    directions: (Direction, Direction, Direction, Direction),
+) -> ProposedMove {
+    // if there are no elves in any of the 8 adjacent points, the elf does nothing
+    // we encode this as proposing to "do nothing" by remaining in the same location
+    if occupied.intersection(&p.adjacent_points()).count() == 0 {
+        return ProposedMove { prev: *p, next: *p };
+    }
+
+    let directions = [directions.0, directions.1, directions.2, directions.3];
+
+    // otherwise, the elf looks in each of the four directions (the order changes
+    // every round) and proposes moving one step in the first valid direction
+    for dir in directions.iter() {
+        match dir {
+            Direction::N => {
+                if occupied.intersection(&p.adjacent_north()).count() == 0 {
+                    return ProposedMove {
+                        prev: *p,
+                        next: p.north(),
+                    };
+                }
+            }
+            Direction::E => {
+                if occupied.intersection(&p.adjacent_east()).count() == 0 {
+                    return ProposedMove {
+                        prev: *p,
+                        next: p.east(),
+                    };
+                }
+            }
+            Direction::S => {
+                if occupied.intersection(&p.adjacent_south()).count() == 0 {
+                    return ProposedMove {
+                        prev: *p,
+                        next: p.south(),
+                    };
+                }
+            }
+            Direction::W => {
+                if occupied.intersection(&p.adjacent_west()).count() == 0 {
+                    return ProposedMove {
+                        prev: *p,
+                        next: p.west(),
+                    };
+                }
+            }
+        }
+    }
+
+    // the current point is completely surrounded and cannot move anywhere (?)
+    ProposedMove { prev: *p, next: *p }
+}
+
+/// Accept/reject each point in the given set of `proposed` moves returning
+/// the "new" state of occupied points for all accepted moves.
+fn accept_reject(proposed: &[ProposedMove]) -> OccupiedPoints {
+    // build a map of the count of each new proposed move
+    let counts = proposed.iter().fold(HashMap::new(), |mut map, val| {
+        map.entry(val.next)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+        map
+    });
+
+    proposed
+        .iter()
+        .map(|p| {
+            // if a proposed move has more than 1 count, reject it; accept it otherwise
+            if let Some(1) = counts.get(&p.next) {
+                p.accept()
+            } else {
+                p.reject()
+            }
+        })
+        .collect()
+}
+
+/// Decode a "grove scan" string into a hash-set of points for each occupied point
+/// (`#`) in the grove. Empty spaces (`.`) are ignored/discarded.
+fn parse_grove_scan(s: &str) -> Result<OccupiedPoints, AocError> {
+    let grid = Grid::parse(s, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(ParseError::Invalid("tile", c.to_string())),
+    })?;
+
+    Ok(grid
+        .iter()
+        .filter(|(_, &elf)| elf)
+        .map(|(p, _)| p)
+        .collect())
+}
+
+/// Draws an `m x n` grid of the occupied points in the given grove
+pub fn draw_grove(op: &OccupiedPoints) -> Grid<bool> {
+    // ( 0,  0) the origin, is the upper-left most point in our coordinate system
+    // ( 0, -1) is one unit North of the origin
+    // ( 1,  0) is one unit East of the origin
+    // ( 0,  1) is one unit South of the origin
+    // (-1,  0) is one unit West of the origin
+    Grid::from_points(op)
+}
+
+/// Counts the number of empty tiles in the smallest rectangle containing every occupied point
+fn empty_tiles(op: &OccupiedPoints) -> u32 {
+    match Bounds::of(op) {
+        Some(bounds) => (bounds.area() - op.len()) as u32,
+        None => 0,
+    }
+}
+
+fn rotate(
+    ds: (Direction, Direction, Direction, Direction),
+) -> (Direction, Direction, Direction, Direction) {
+    (ds.1, ds.2, ds.3, ds.0)
+}
+
+/// Simulates the given number of rounds of the elves spreading out
+pub fn simulate(occupied: &OccupiedPoints, rounds: u32) -> OccupiedPoints {
+    let mut directions = (Direction::N, Direction::S, Direction::W, Direction::E);
+    let mut occupied = occupied.clone();
+
+    for _ in 0..rounds {
+        let proposed = occupied
+            .iter()
+            .map(|p| propose_move(p, &occupied, directions))
+            .collect::<Vec<_>>();
+        occupied = accept_reject(&proposed);
+
+        directions = rotate(directions);
+    }
+
+    occupied
+}
+
+impl Solution for Day23 {
+    type Input = OccupiedPoints;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        // initial state (round 0)
+        parse_grove_scan(input)
+    }
+
+    fn part1(op: &Self::Input) -> Result<Self::Part1, AocError> {
+        let occupied = simulate(op, 10);
+        Ok(empty_tiles(&occupied))
+    }
+
+    fn part2(op: &Self::Input) -> Result<Self::Part2, AocError> {
+        let mut directions = (Direction::N, Direction::S, Direction::W, Direction::E);
+        let mut prev = op.clone();
+        let mut curr = op.clone();
+
+        // start counting rounds from 1 onwards
+        let mut i = 1;
+        loop {
+            let proposed = curr
+                .iter()
+                .map(|p| propose_move(p, &curr, directions))
+                .collect::<Vec<_>>();
+            curr = accept_reject(&proposed);
+
+            if prev == curr {
+                return Ok(i);
+            }
+            directions = rotate(directions);
+            prev = curr.clone();
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+
+    const EXAMPLE: &str = "\
+....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    aoc_example!(Day23, EXAMPLE, part1 = 110, part2 = 20);
+}
@@ -1,36 +1,34 @@
-use std::env;
-use std::error::Error;
-use std::fs::read_to_string;
+use aoc_common::error::AocError;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::solution::Solution;
+
+pub struct Day4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Range {
+pub struct Range {
     min: i32,
     max: i32,
 }
 
 impl Range {
-    fn parse(s: &str) -> Self {
-        let parts: Vec<&str> = s.split('-').collect();
-        match parts[..] {
-            [a, b] => {
-                let a = a.to_string().parse::<i32>().unwrap();
-                let b = b.to_string().parse::<i32>().unwrap();
-                Range { min: a, max: b }
-            }
-            _ => panic!("Error parsing unknown range format"),
-        }
+    fn parse(s: &str) -> Result<Self, ParseError> {
+        let (a, b) = s.split_once('-').ok_or(ParseError::Missing("`-`"))?;
+        let bound = |n: &str| {
+            n.parse::<i32>().map_err(|_| {
+                ParseError::Invalid("section", n.to_string()).at(parse::offset_of(s, n))
+            })
+        };
+
+        Ok(Range {
+            min: bound(a)?,
+            max: bound(b)?,
+        })
     }
 
-    fn parse_pair(s: &str) -> (Self, Self) {
-        let parts: Vec<&str> = s.split(',').collect();
-        match parts[..] {
-            [a, b] => {
-                let a = Self::parse(a);
-                let b = Self::parse(b);
-                (a, b)
-            }
-            _ => panic!("Error parsing unknown range pair format"),
-        }
+    fn parse_pair(s: &str) -> Result<(Self, Self), ParseError> {
+        let (a, b) = s.split_once(',').ok_or(ParseError::Missing("`,`"))?;
+        let second = |e: ParseError| e.offset_by(a.len() + 1);
+        Ok((Self::parse(a)?, Self::parse(b).map_err(second)?))
     }
 
     fn contains(&self, other: &Self) -> bool {
@@ -40,7 +38,7 @@ impl Range {
     fn overlaps(&self, other: &Self) -> bool {
         ((self.min <= other.min) & (self.max >= other.min))
             | ((self.min <= other.max) & (self.max >= other.max))
-            | other.contains(&self)
+            | other.contains(self)
     }
 }
 
@@ -48,61 +46,39 @@ fn either_contains(a: &Range, b: &Range) -> bool {
     a.contains(b) | b.contains(a)
 }
 
-fn part1(input: &String) -> Result<(), Box<dyn Error>> {
-    let total: i32 = input
-        .split('\n')
-        .into_iter()
-        .map(|s| {
-            let (a, b) = Range::parse_pair(s);
-            match either_contains(&a, &b) {
-                true => 1,
-                false => 0,
-            }
-        })
-        .sum();
-
-    println!("Part I: {total}");
-    Ok(())
-}
+impl Solution for Day4 {
+    type Input = Vec<(Range, Range)>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-fn part2(input: &String) -> Result<(), Box<dyn Error>> {
-    let total: i32 = input
-        .split('\n')
-        .into_iter()
-        .map(|s| {
-            let (a, b) = Range::parse_pair(s);
-            match a.overlaps(&b) {
-                true => 1,
-                false => 0,
-            }
-        })
-        .sum();
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse::lines_with(input, Range::parse_pair)
+    }
 
-    println!("Part II: {total}");
-    Ok(())
-}
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError> {
+        let total = input.iter().filter(|(a, b)| either_contains(a, b)).count();
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let path = &args[1];
-    let input = read_to_string(path)?.trim().to_string();
+        Ok(total)
+    }
 
-    part1(&input)?;
-    part2(&input)?;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError> {
+        let total = input.iter().filter(|(a, b)| a.overlaps(b)).count();
 
-    Ok(())
+        Ok(total)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::aoc_example;
 
     #[test]
     fn test_range_parse() {
         let r = "1-2";
         let range = Range { min: 1, max: 2 };
 
-        assert_eq!(Range::parse(r), range)
+        assert_eq!(Range::parse(r), Ok(range))
     }
 
     #[test]
@@ -111,7 +87,20 @@ mod tests {
         let a = Range { min: 1, max: 2 };
         let b = Range { min: 3, max: 4 };
 
-        assert_eq!(Range::parse_pair(pair), (a, b))
+        assert_eq!(Range::parse_pair(pair), Ok((a, b)))
+    }
+
+    #[test]
+    fn test_range_parse_invalid() {
+        assert_eq!(
+            Range::parse("1-x"),
+            Err(ParseError::Invalid("section", "x".to_string()).at(2))
+        );
+        assert_eq!(
+            Range::parse_pair("1-2,1-x"),
+            Err(ParseError::Invalid("section", "x".to_string()).at(6))
+        );
+        assert_eq!(Range::parse_pair("1-2"), Err(ParseError::Missing("`,`")));
     }
 
     #[test]
@@ -168,4 +157,15 @@ mod tests {
         assert!(a.overlaps(&b));
         assert!(b.overlaps(&a));
     }
+
+    const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    aoc_example!(Day4, EXAMPLE, part1 = 2, part2 = 4);
 }
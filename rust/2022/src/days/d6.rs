@@ -0,0 +1,64 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+
+use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+
+pub struct Day6;
+
+/// Returns the number of characters processed before the first marker of `n` distinct
+/// characters has been received, if there is one
+fn decoder(s: &str, n: i32) -> Option<i32> {
+    let n = n as usize;
+    let chars = s.chars().collect::<Vec<_>>();
+    let index = chars
+        .windows(n)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let set: HashSet<_, RandomState> = HashSet::from_iter(w);
+            // add n since enumerating begins counting the first window at 0
+            let j = i + n;
+
+            if set.len() == n {
+                Some(j)
+            } else {
+                None
+            }
+        })
+        // we only care about the first marker occurrence; discard the rest
+        .next()?;
+
+    Some(index as i32)
+}
+
+impl Solution for Day6 {
+    type Input = String;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError> {
+        decoder(input, 4).ok_or(AocError::solve("No start-of-packet marker found"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError> {
+        decoder(input, 14).ok_or(AocError::solve("No start-of-message marker found"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::aoc_example;
+
+    aoc_example!(
+        Day6,
+        "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+        part1 = 7,
+        part2 = 19
+    );
+    aoc_example!(example_2: Day6, "bvwbjplbgvbhsrlpgdmjqwftvncz", part1 = 5, part2 = 23);
+}
@@ -0,0 +1,18 @@
+use aoc_common::solution::{run, Runner};
+
+pub mod d10;
+pub mod d19;
+pub mod d21;
+pub mod d23;
+pub mod d4;
+pub mod d6;
+
+/// Every implemented solution keyed by its day number, in ascending order
+pub const SOLUTIONS: &[(u8, Runner)] = &[
+    (4, run::<d4::Day4>),
+    (6, run::<d6::Day6>),
+    (10, run::<d10::Day10>),
+    (19, run::<d19::Day19>),
+    (21, run::<d21::Day21>),
+    (23, run::<d23::Day23>),
+];
@@ -0,0 +1,4 @@
+pub mod days;
+
+/// The Advent of Code event year solved by this crate
+pub const YEAR: u16 = 2022;
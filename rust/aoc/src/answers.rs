@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use aoc_common::error::AocError;
+use aoc_common::input::{InputError, Resolver, Source};
+use aoc_common::parse::ParseError;
+use toml::Spanned;
+
+/// The location of the recorded answers for the given `year`, relative to the data directory
+pub fn path(year: u16) -> PathBuf {
+    PathBuf::from(format!("answers/{year}.toml"))
+}
+
+/// The outcome of comparing a computed answer against the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail { .. } => write!(f, "FAIL"),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Verified answers keyed by day and part, loaded from a TOML file such as:
+///
+/// ```toml
+/// [4]
+/// part1 = 2
+/// part2 = 4
+///
+/// [10]
+/// part1 = 13140
+/// ```
+#[derive(Debug, Default)]
+pub struct Expected {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl Expected {
+    /// Parses the recorded answers from the contents of an answers file
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        // keys and values keep their spans, so that errors point at the offending one
+        type Table = BTreeMap<Spanned<String>, Spanned<toml::Value>>;
+        let table: BTreeMap<Spanned<String>, Spanned<Table>> =
+            toml::from_str(s).map_err(|e: toml::de::Error| {
+                let offset = e.span().map_or(0, |span| span.start);
+                AocError::parse_at(s, offset, ParseError::Syntax(e.message().to_string()))
+            })?;
+        let mut answers = HashMap::new();
+
+        let error = |at: usize, error: ParseError| AocError::parse_at(s, at, error);
+
+        for (day, parts) in table.iter() {
+            let day_number: u8 = day.get_ref().parse().map_err(|_| {
+                error(
+                    day.span().start,
+                    ParseError::Invalid("day", day.get_ref().clone()),
+                )
+            })?;
+
+            for (part, value) in parts.get_ref().iter() {
+                let part_number = match part.get_ref().as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    name => {
+                        let e = ParseError::Invalid("part", name.to_string());
+                        return Err(error(part.span().start, e));
+                    }
+                };
+                let value = match value.get_ref() {
+                    toml::Value::Integer(i) => i.to_string(),
+                    toml::Value::String(s) => s.clone(),
+                    other => {
+                        let e = ParseError::Invalid("answer", other.to_string());
+                        return Err(error(value.span().start, e));
+                    }
+                };
+                let (day, part) = (day_number, part_number);
+
+                answers.insert((day, part), value);
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// Loads the recorded answers from `path`; a missing file has no recorded answers
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+
+        match read_to_string(path) {
+            // name the file in the error, since it is not the puzzle input
+            Ok(s) => Self::parse(&s).map_err(|e| match e {
+                AocError::Parse {
+                    line,
+                    column,
+                    text,
+                    error,
+                } => AocError::Parse {
+                    line,
+                    column,
+                    text,
+                    error: ParseError::Syntax(format!("{}: {error}", path.display())),
+                },
+                e => e,
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(InputError::Io {
+                path: path.to_path_buf(),
+                error,
+            }
+            .into()),
+        }
+    }
+
+    /// Loads the recorded answers of the `year` that apply to the input being run. Only
+    /// the puzzle input found through the `resolver` has recorded answers, so the answers
+    /// for an example or an explicit `input` file are all unknown.
+    pub fn load_for(
+        resolver: &Resolver,
+        year: u16,
+        source: Source,
+        input: Option<&Path>,
+    ) -> Result<Self, AocError> {
+        match (source, input) {
+            (Source::Input, None) => Self::load(resolver.root().join(path(year))),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Compares the `actual` answer for a given `day` and `part` with the recorded answer.
+    /// Trailing whitespace is ignored so that multi-line answers can be recorded naturally.
+    pub fn check(&self, day: u8, part: u8, actual: &str) -> Verdict {
+        match self.answers.get(&(day, part)) {
+            None => Verdict::Unknown,
+            Some(expected) if expected.trim_end() == actual.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[4]
+part1 = 2
+part2 = "4"
+
+[10]
+part2 = """
+##..
+..##
+"""
+"#;
+
+    #[test]
+    fn test_check() {
+        let expected = Expected::parse(ANSWERS).unwrap();
+
+        assert_eq!(expected.check(4, 1, "2"), Verdict::Pass);
+        assert_eq!(expected.check(4, 2, "4"), Verdict::Pass);
+        assert_eq!(
+            expected.check(4, 1, "3"),
+            Verdict::Fail {
+                expected: "2".to_string()
+            }
+        );
+        assert_eq!(expected.check(10, 1, "13140"), Verdict::Unknown);
+        assert_eq!(expected.check(10, 2, "##..\n..##\n"), Verdict::Pass);
+    }
+
+    #[test]
+    fn test_load_for() {
+        let root = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("answers")).unwrap();
+        std::fs::write(root.join(path(2022)), ANSWERS).unwrap();
+        let resolver = Resolver::new(&root);
+        let check = |source, input: Option<&Path>| {
+            Expected::load_for(&resolver, 2022, source, input)
+                .unwrap()
+                .check(4, 1, "2")
+        };
+
+        assert_eq!(check(Source::Input, None), Verdict::Pass);
+        assert_eq!(check(Source::Example(1), None), Verdict::Unknown);
+        assert_eq!(
+            check(Source::Input, Some(Path::new("4.txt"))),
+            Verdict::Unknown
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_invalid_part() {
+        let err = Expected::parse("[4]\npart3 = 1").unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_span() {
+        // the offending key is located by its span, not by the earlier `part3` string
+        let err = Expected::parse(
+            "[4]
+part1 = \"part3\"
+part3 = 1",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+
+        let err = Expected::parse(
+            "[4]
+part1 = 1.5",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: 2,
+                column: 9,
+                ..
+            }
+        ));
+
+        assert!(Expected::parse("4 = 1").is_err());
+    }
+}
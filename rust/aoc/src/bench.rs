@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use aoc_common::error::AocError;
+use aoc_common::solution::{Answers, Parts, Runner, Timings};
+
+/// Summary statistics over a set of timing samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the given samples, returning `None` if there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+
+        let n = samples.len();
+        let median = match n {
+            0 => return None,
+            n if n % 2 == 1 => samples[n / 2],
+            n => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        };
+
+        Some(Self {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        })
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "max_ns": self.max.as_nanos() as u64,
+        })
+    }
+}
+
+/// The timing statistics for each stage of a solution over one or more runs. The
+/// statistics for a part are `None` if that part was not solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Benchmark {
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+impl Benchmark {
+    /// Summarizes the timings of each run, returning `None` if there were no runs
+    pub fn from_timings(timings: &[Timings]) -> Option<Self> {
+        let part = |f: fn(&Timings) -> Option<Duration>| {
+            Stats::from_samples(&timings.iter().filter_map(f).collect::<Vec<_>>())
+        };
+        let parse = timings.iter().map(|t| t.parse).collect::<Vec<_>>();
+
+        Some(Self {
+            runs: timings.len(),
+            parse: Stats::from_samples(&parse)?,
+            part1: part(|t| t.part1),
+            part2: part(|t| t.part2),
+        })
+    }
+
+    /// The statistics for solving the given `part` (1 or 2), if it was solved
+    pub fn part(&self, part: u8) -> Option<Stats> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    /// Iterates over the statistics of each stage that was run, along with its name
+    pub fn stages(&self) -> impl Iterator<Item = (&'static str, Stats)> {
+        [
+            ("parse", Some(self.parse)),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+        .into_iter()
+        .filter_map(|(stage, stats)| Some((stage, stats?)))
+    }
+}
+
+/// Runs a solution `runs` times against the same input, returning the answers
+/// from the final run along with the timing statistics across all runs
+pub fn measure(
+    runner: Runner,
+    input: &str,
+    parts: Parts,
+    runs: usize,
+) -> Result<(Answers, Benchmark), AocError> {
+    let mut answers = None;
+    let mut timings = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let (a, t) = runner(input, parts)?;
+        answers = Some(a);
+        timings.push(t);
+    }
+
+    let answers = answers.ok_or("Benchmark requires at least one run")?;
+    let benchmark = Benchmark::from_timings(&timings).ok_or("No timings recorded")?;
+
+    Ok((answers, benchmark))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats_odd() {
+        let stats = Stats::from_samples(&[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.max, ms(5));
+    }
+
+    #[test]
+    fn test_stats_even() {
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.max, ms(8));
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
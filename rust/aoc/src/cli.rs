@@ -0,0 +1,295 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use aoc_common::input::Source;
+use aoc_common::solution::Parts;
+
+use crate::years;
+
+pub const USAGE: &str = "\
+usage: aoc [<day>|all] [options]
+       aoc new <year> <day>
+
+Runs the Advent of Code solutions and verifies the answers, or generates the
+module, example test and placeholder input files for a new day with `new`.
+
+Options:
+  -y, --year <year>      run the solutions for a single year, or `all` years (default: latest)
+  -d, --day <day>        run a single day (1-25), or `all` days (default: all)
+  -p, --part <part>      only solve part 1 or 2 (default: both)
+  -i, --input <path>     read the input from <path> instead of inputs/<year>/<day>.txt
+  -e, --example <n>      read examples/<year>/<day>-<n>.txt instead of the puzzle input
+  -b, --bench <runs>     run each day <runs> times and report min/median/max timings
+  -f, --format <format>  output format: text, json or ndjson (default: text)
+  -q, --quiet            only print the answers
+  -h, --help             print this help message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    /// A single JSON array with one record per answer
+    Json,
+    /// One JSON record per answer on each line
+    Ndjson,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Years {
+    All,
+    One(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Days {
+    All,
+    One(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub years: Years,
+    pub days: Days,
+    pub parts: Parts,
+    pub input: Option<PathBuf>,
+    pub source: Source,
+    pub runs: usize,
+    pub format: Format,
+    pub quiet: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            years: Years::One(years::latest().year),
+            days: Days::All,
+            parts: Parts::Both,
+            input: None,
+            source: Source::Input,
+            runs: 1,
+            format: Format::Text,
+            quiet: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(Options),
+    New { year: u16, day: u8 },
+    Help,
+}
+
+/// An invalid command line, reported alongside the usage message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn parse_years(s: &str) -> Result<Years, CliError> {
+    match s {
+        "all" => Ok(Years::All),
+        _ => match s.parse::<u16>().ok().and_then(years::get) {
+            Some(year) => Ok(Years::One(year.year)),
+            None => {
+                let known: Vec<_> = years::YEARS.iter().map(|y| y.year.to_string()).collect();
+                Err(CliError(format!(
+                    "Invalid year `{s}`, expected one of {} or `all`",
+                    known.join(", ")
+                )))
+            }
+        },
+    }
+}
+
+fn parse_days(s: &str) -> Result<Days, CliError> {
+    match s {
+        "all" => Ok(Days::All),
+        _ => match s.parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(Days::One(day)),
+            _ => Err(CliError(format!(
+                "Invalid day `{s}`, expected 1-25 or `all`"
+            ))),
+        },
+    }
+}
+
+fn parse_parts(s: &str) -> Result<Parts, CliError> {
+    match s {
+        "1" => Ok(Parts::Part1),
+        "2" => Ok(Parts::Part2),
+        _ => Err(CliError(format!("Invalid part `{s}`, expected 1 or 2"))),
+    }
+}
+
+fn parse_positive(s: &str, name: &str) -> Result<usize, CliError> {
+    match s.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(CliError(format!(
+            "Invalid {name} `{s}`, expected a positive number"
+        ))),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, CliError> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "ndjson" => Ok(Format::Ndjson),
+        _ => Err(CliError(format!(
+            "Invalid format `{s}`, expected `text`, `json` or `ndjson`"
+        ))),
+    }
+}
+
+/// Parses the arguments of the `new` subcommand
+fn parse_new(args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let args: Vec<String> = args.collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Command::Help);
+    }
+
+    let [year, day] = args.as_slice() else {
+        return Err(CliError("Expected `new <year> <day>`".to_string()));
+    };
+    let year = match year.parse::<u16>() {
+        Ok(year @ 2015..) => year,
+        _ => return Err(CliError(format!("Invalid year `{year}`"))),
+    };
+    let Days::One(day) = parse_days(day)? else {
+        return Err(CliError("Expected a single day".to_string()));
+    };
+
+    Ok(Command::New { year, day })
+}
+
+/// Parses the command line arguments (excluding the program name)
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, CliError> {
+    let mut args = args.into_iter().peekable();
+    if args.peek().is_some_and(|arg| arg == "new") {
+        return parse_new(args.skip(1));
+    }
+    let mut opts = Options::default();
+    let mut day = None;
+
+    while let Some(arg) = args.next() {
+        // support both `--flag value` and `--flag=value`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| match inline {
+            Some(v) => Ok(v.to_string()),
+            None => args
+                .next()
+                .ok_or(CliError(format!("Missing value for {name}"))),
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-y" | "--year" => opts.years = parse_years(&value(&flag)?)?,
+            "-d" | "--day" => day = Some(parse_days(&value(&flag)?)?),
+            "-p" | "--part" => opts.parts = parse_parts(&value(&flag)?)?,
+            "-i" | "--input" => opts.input = Some(PathBuf::from(value(&flag)?)),
+            "-e" | "--example" => {
+                let n = parse_positive(&value(&flag)?, "example")?;
+                opts.source = Source::Example(n as u32);
+            }
+            "-b" | "--bench" => opts.runs = parse_positive(&value(&flag)?, "number of runs")?,
+            "-f" | "--format" => opts.format = parse_format(&value(&flag)?)?,
+            "-q" | "--quiet" => opts.quiet = true,
+            s if s.starts_with('-') => return Err(CliError(format!("Unknown option `{s}`"))),
+            s if day.is_none() => day = Some(parse_days(s)?),
+            s => return Err(CliError(format!("Unexpected argument `{s}`"))),
+        }
+    }
+
+    opts.days = day.unwrap_or(Days::All);
+
+    if opts.input.is_some() && opts.days == Days::All {
+        return Err(CliError("--input requires a single --day".to_string()));
+    }
+    if opts.input.is_some() && opts.years == Years::All {
+        return Err(CliError("--input requires a single --year".to_string()));
+    }
+    if opts.input.is_some() && opts.source != Source::Input {
+        return Err(CliError(
+            "--input and --example cannot be used together".to_string(),
+        ));
+    }
+
+    Ok(Command::Run(opts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    fn run(s: &str) -> Options {
+        match parse(args(s)) {
+            Ok(Command::Run(opts)) => opts,
+            other => panic!("expected options, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(run(""), Options::default());
+    }
+
+    #[test]
+    fn test_positional_day() {
+        assert_eq!(run("21").days, Days::One(21));
+        assert_eq!(run("all").days, Days::All);
+    }
+
+    #[test]
+    fn test_flags() {
+        let opts = run("--year 2021 --day 10 -p 2 --example=1 -b 5 --format ndjson -q");
+
+        assert_eq!(opts.years, Years::One(2021));
+        assert_eq!(opts.days, Days::One(10));
+        assert_eq!(opts.parts, Parts::Part2);
+        assert_eq!(opts.source, Source::Example(1));
+        assert_eq!(opts.runs, 5);
+        assert_eq!(opts.format, Format::Ndjson);
+        assert!(opts.quiet);
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            parse(args("new 2022 7")),
+            Ok(Command::New { year: 2022, day: 7 })
+        );
+        assert!(parse(args("new 2022")).is_err());
+        assert!(parse(args("new 2022 all")).is_err());
+        assert!(parse(args("new 1999 7")).is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        assert_eq!(parse(args("4 --help")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(args("--year 2014")).is_err());
+        assert!(parse(args("--year all --day 1 --input in.txt")).is_err());
+        assert!(parse(args("--day 26")).is_err());
+        assert!(parse(args("--part 3")).is_err());
+        assert!(parse(args("--bench 0")).is_err());
+        assert!(parse(args("--format yaml")).is_err());
+        assert!(parse(args("--input")).is_err());
+        assert!(parse(args("--input in.txt")).is_err());
+        assert!(parse(args("4 5")).is_err());
+        assert!(parse(args("--verbose")).is_err());
+    }
+}
@@ -0,0 +1,392 @@
+mod answers;
+mod bench;
+mod cli;
+mod scaffold;
+mod years;
+
+use std::env;
+use std::error::Error;
+use std::process;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::input::{self, Resolver};
+use aoc_common::solution::{Answers, Runner};
+
+use answers::{Expected, Verdict};
+use bench::Benchmark;
+use cli::{Command, Days, Format, Options, Years};
+use years::Year;
+
+/// The outcome of running (and verifying) the solution for a single day
+struct DayResult {
+    year: u16,
+    day: u8,
+    answers: Answers,
+    verdicts: Vec<(u8, Verdict)>,
+    benchmark: Benchmark,
+}
+
+impl DayResult {
+    /// Returns `true` if any answer did not match the recorded one
+    fn mismatched(&self) -> bool {
+        self.verdicts.iter().any(|(_, v)| v.is_fail())
+    }
+
+    /// Returns `true` if any part could not be solved
+    fn errored(&self) -> bool {
+        self.answers.iter().any(|(_, answer)| answer.is_err())
+    }
+
+    fn verdict(&self, part: u8) -> &Verdict {
+        self.verdicts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map_or(&Verdict::Unknown, |(_, v)| v)
+    }
+}
+
+/// A day whose solution could not be run at all, such as one with a missing input or
+/// an input that cannot be parsed
+struct DayError {
+    year: u16,
+    day: u8,
+    error: AocError,
+}
+
+/// The outcome of a single day, in the order the days were run
+type Outcome = Result<DayResult, DayError>;
+
+fn part_label(part: u8) -> &'static str {
+    match part {
+        1 => "Part I",
+        _ => "Part II",
+    }
+}
+
+/// Prints a single answer along with its verdict, placing multi-line answers
+/// (such as images) below the label
+fn print_answer(label: &str, answer: &Answer, verdict: &Verdict) {
+    if answer.is_multiline() {
+        println!("{label}: [{verdict}]\n\n{answer}");
+    } else {
+        println!("{label}: {answer} [{verdict}]");
+    }
+
+    if let Verdict::Fail { expected } = verdict {
+        println!("  expected: {expected}");
+    }
+}
+
+fn fmt_duration(d: Duration) -> String {
+    format!("{d:.1?}")
+}
+
+/// Prints the answers for each day followed by a table of the timings for each stage
+fn print_text(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        let r = match outcome {
+            Ok(r) => r,
+            Err(DayError { year, day, error }) => {
+                println!("{year} Day {day}\nError: {error}\n");
+                continue;
+            }
+        };
+        println!("{} Day {}", r.year, r.day);
+        for (part, answer) in r.answers.iter() {
+            match answer {
+                Ok(answer) => print_answer(part_label(part), answer, r.verdict(part)),
+                Err(e) => println!("{}: Error: {e}", part_label(part)),
+            }
+        }
+        println!();
+    }
+
+    let results: Vec<_> = outcomes.iter().flatten().collect();
+    if results.is_empty() {
+        return;
+    }
+
+    println!(
+        "{:>4} {:>3}  {:<6} {:>6} {:>12} {:>12} {:>12}",
+        "year", "day", "stage", "runs", "min", "median", "max"
+    );
+    for r in results {
+        let b = &r.benchmark;
+        for (stage, stats) in b.stages() {
+            println!(
+                "{:>4} {:>3}  {:<6} {:>6} {:>12} {:>12} {:>12}",
+                r.year,
+                r.day,
+                stage,
+                b.runs,
+                fmt_duration(stats.min),
+                fmt_duration(stats.median),
+                fmt_duration(stats.max),
+            );
+        }
+    }
+}
+
+/// Prints only the answers, one per line
+fn print_quiet(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        let r = match outcome {
+            Ok(r) => r,
+            Err(DayError { year, day, error }) => {
+                eprintln!("Error in {year} day {day}: {error}");
+                continue;
+            }
+        };
+        for (part, answer) in r.answers.iter() {
+            match answer {
+                Ok(answer) => println!("{answer}"),
+                Err(e) => eprintln!("Error in {} day {} part {part}: {e}", r.year, r.day),
+            }
+        }
+    }
+}
+
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(n) => json!(n),
+        _ => json!(answer.to_string()),
+    }
+}
+
+/// Flattens the results into one record per answer, with the timings of the parse
+/// stage and of solving that part. A part that could not be solved has an `error`
+/// in place of its answer, and a day that could not be run at all has a single record
+/// with an `error` and no part.
+fn records(outcomes: &[Outcome]) -> Vec<Value> {
+    outcomes
+        .iter()
+        .flat_map(|outcome| -> Vec<Value> {
+            let r = match outcome {
+                Ok(r) => r,
+                Err(DayError { year, day, error }) => {
+                    return vec![json!({
+                        "year": year,
+                        "day": day,
+                        "error": error.to_string(),
+                    })]
+                }
+            };
+            r.answers
+                .iter()
+                .map(move |(part, answer)| {
+                    let b = &r.benchmark;
+                    let mut timings = json!({ "parse": b.parse.to_json() });
+                    if let Some(stats) = b.part(part) {
+                        timings["solve"] = stats.to_json();
+                    }
+
+                    let mut record = json!({
+                        "year": r.year,
+                        "day": r.day,
+                        "part": part,
+                        "runs": b.runs,
+                        "timings": timings,
+                    });
+                    match answer {
+                        Ok(answer) => {
+                            record["type"] = json!(answer.kind());
+                            record["answer"] = answer_json(answer);
+                            record["verdict"] = json!(r.verdict(part).to_string());
+                        }
+                        Err(e) => record["error"] = json!(e.to_string()),
+                    }
+                    record
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn print_json(outcomes: &[Outcome]) {
+    println!("{}", Value::Array(records(outcomes)));
+}
+
+fn print_ndjson(outcomes: &[Outcome]) {
+    for record in records(outcomes) {
+        println!("{record}");
+    }
+}
+
+/// Runs the solution for a single day `runs` times and verifies the answers
+fn run_day(
+    year: u16,
+    day: u8,
+    runner: Runner,
+    input: &str,
+    opts: &Options,
+    expected: &Expected,
+) -> Result<DayResult, AocError> {
+    let (answers, benchmark) = bench::measure(runner, input, opts.parts, opts.runs)?;
+
+    let verdicts = answers
+        .iter()
+        .filter_map(|(part, answer)| {
+            let answer = answer.as_ref().ok()?;
+            Some((part, expected.check(day, part, &answer.to_string())))
+        })
+        .collect();
+
+    Ok(DayResult {
+        year,
+        day,
+        answers,
+        verdicts,
+        benchmark,
+    })
+}
+
+/// Runs the selected days of a single year, appending the outcome of each day. When
+/// running every day, a day that fails is recorded and the remaining days still run.
+fn run_year(
+    year: &Year,
+    opts: &Options,
+    resolver: &Resolver,
+    outcomes: &mut Vec<Outcome>,
+) -> Result<(), AocError> {
+    let expected = Expected::load_for(resolver, year.year, opts.source, opts.input.as_deref())?;
+
+    match opts.days {
+        Days::All => {
+            for (day, runner) in year.solutions {
+                let result = resolver
+                    .read(year.year, *day, opts.source)
+                    .map_err(AocError::from)
+                    .and_then(|input| run_day(year.year, *day, *runner, &input, opts, &expected));
+
+                outcomes.push(result.map_err(|error| DayError {
+                    year: year.year,
+                    day: *day,
+                    error,
+                }));
+            }
+        }
+        Days::One(day) => {
+            let runner = year.get(day).ok_or(AocError::NoSolution {
+                year: year.year,
+                day: Some(day),
+            })?;
+            let input = match &opts.input {
+                Some(path) => input::read_path(path)?,
+                None => resolver.read(year.year, day, opts.source)?,
+            };
+
+            outcomes.push(Ok(run_day(
+                year.year, day, runner, &input, opts, &expected,
+            )?));
+        }
+    }
+
+    Ok(())
+}
+
+fn run(opts: Options) -> Result<(), AocError> {
+    let resolver = Resolver::from_env();
+    let mut outcomes = vec![];
+
+    match opts.years {
+        Years::All => {
+            for year in years::YEARS {
+                // a day only needs to be implemented in some of the years
+                if let Days::One(day) = opts.days {
+                    if year.get(day).is_none() {
+                        continue;
+                    }
+                }
+                run_year(year, &opts, &resolver, &mut outcomes)?;
+            }
+        }
+        Years::One(year) => {
+            let year = years::get(year).ok_or(AocError::NoSolution { year, day: None })?;
+            run_year(year, &opts, &resolver, &mut outcomes)?;
+        }
+    }
+
+    match (opts.format, opts.quiet) {
+        (Format::Text, false) => print_text(&outcomes),
+        (Format::Text, true) => print_quiet(&outcomes),
+        (Format::Json, _) => print_json(&outcomes),
+        (Format::Ndjson, _) => print_ndjson(&outcomes),
+    }
+
+    let errored = outcomes
+        .iter()
+        .any(|r| r.as_ref().map_or(true, DayResult::errored));
+    if outcomes.iter().flatten().any(|r| r.mismatched()) {
+        if !opts.quiet {
+            eprintln!(
+                "One or more answers did not match those recorded under {}",
+                resolver.root().join("answers").display()
+            );
+        }
+        process::exit(1);
+    }
+    if errored {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Generates the skeleton of a new day and lists the files that were written
+fn new_day(year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+    let paths = scaffold::new_day(
+        &scaffold::workspace_root(),
+        &Resolver::from_env(),
+        year,
+        day,
+    )?;
+
+    for path in paths {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let result = match cli::parse(env::args().skip(1)) {
+        Ok(Command::Run(opts)) => run(opts).map_err(|e| e.into()),
+        Ok(Command::New { year, day }) => new_day(year, day),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Error: {e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_day_record() {
+        let outcomes = [Err(DayError {
+            year: 2022,
+            day: 4,
+            error: AocError::solve("No input"),
+        })];
+
+        assert_eq!(
+            records(&outcomes),
+            vec![json!({ "year": 2022, "day": 4, "error": "No input" })]
+        );
+    }
+}
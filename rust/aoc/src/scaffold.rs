@@ -0,0 +1,204 @@
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use aoc_common::input::{Resolver, Source};
+
+/// The root of the Cargo workspace, which holds one directory per year
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Renders the skeleton module for a new day
+fn template(day: u8) -> String {
+    format!(
+        r#"use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {{
+        Ok(input.trim().lines().map(|s| s.to_string()).collect())
+    }}
+
+    fn part1(_input: &Self::Input) -> Result<Self::Part1, AocError> {{
+        Err("Part I has not been solved yet".into())
+    }}
+
+    fn part2(_input: &Self::Input) -> Result<Self::Part2, AocError> {{
+        Err("Part II has not been solved yet".into())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use aoc_common::aoc_example;
+
+    // paste the example from the puzzle text here
+    const EXAMPLE: &str = "\
+";
+
+    aoc_example!(
+        #[ignore = "the example has not been filled in yet"]
+        Day{day},
+        EXAMPLE,
+        part1 = 0,
+        part2 = 0,
+    );
+}}
+"#
+    )
+}
+
+/// Registers the module for `day` in the contents of a `days/mod.rs`, declaring
+/// the module and adding its solution to `SOLUTIONS` in day order
+fn register(mod_rs: &str, day: u8) -> Result<String, Box<dyn Error>> {
+    let module = format!("pub mod d{day};");
+    let entry = format!("    ({day}, run::<d{day}::Day{day}>),");
+
+    if mod_rs.lines().any(|line| line == module) {
+        return Err(format!("Module d{day} is already declared").into());
+    }
+
+    let mut lines: Vec<String> = mod_rs.lines().map(|s| s.to_string()).collect();
+
+    // module declarations are kept sorted by name, as rustfmt orders them
+    let modules: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod "))
+        .collect();
+    let last = *modules.last().ok_or("No module declarations found")?;
+    let at = modules
+        .iter()
+        .find(|&&i| lines[i] > module)
+        .map_or(last + 1, |&i| i);
+    lines.insert(at, module);
+
+    // solutions are kept sorted by day number
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLUTIONS"))
+        .ok_or("No SOLUTIONS table found")?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line == "];")
+        .map(|i| start + i)
+        .ok_or("Unterminated SOLUTIONS table")?;
+    let at = (start + 1..end)
+        .find(|&i| {
+            let number = lines[i].trim_start().trim_start_matches('(');
+            let number = number.split(',').next().unwrap_or_default();
+            number.parse::<u8>().is_ok_and(|d| d > day)
+        })
+        .unwrap_or(end);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates an empty file at `path` (and its parent directories), unless it already
+/// exists. Returns `true` if the file was created.
+fn touch(path: &Path) -> Result<bool, Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(format!("{}: {e}", path.display()).into()),
+    }
+}
+
+/// Generates the module for a new day of `year` under `root`, registers it with the
+/// runner and creates empty placeholder files for its input and first example.
+/// Returns the paths of every file that was created or modified.
+pub fn new_day(
+    root: &Path,
+    resolver: &Resolver,
+    year: u16,
+    day: u8,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let days = root.join(year.to_string()).join("src").join("days");
+    let mod_path = days.join("mod.rs");
+    let module_path = days.join(format!("d{day}.rs"));
+
+    if !mod_path.exists() {
+        return Err(format!(
+            "{} not found: days can only be generated for years with a single crate and a src/days module",
+            mod_path.display()
+        )
+        .into());
+    }
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()).into());
+    }
+
+    let mod_rs = fs::read_to_string(&mod_path)?;
+    let mod_rs = register(&mod_rs, day)?;
+
+    fs::write(&module_path, template(day))?;
+    fs::write(&mod_path, mod_rs)?;
+    let mut paths = vec![module_path, mod_path];
+
+    for source in [Source::Input, Source::Example(1)] {
+        let path = resolver.path(year, day, source);
+        if touch(&path)? {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "\
+use aoc_common::solution::{run, Runner};
+
+pub mod d10;
+pub mod d3;
+pub mod d6;
+
+/// Every implemented solution keyed by its day number, in ascending order
+pub const SOLUTIONS: &[(u8, Runner)] = &[
+    (3, run::<d3::Day3>),
+    (6, run::<d6::Day6>),
+    (10, run::<d10::Day10>),
+];
+";
+
+    #[test]
+    fn test_register() {
+        let mod_rs = register(MOD_RS, 5).unwrap();
+
+        assert!(mod_rs.contains("pub mod d3;\npub mod d5;\npub mod d6;\n"));
+        assert!(mod_rs.contains(
+            "    (3, run::<d3::Day3>),\n    (5, run::<d5::Day5>),\n    (6, run::<d6::Day6>),\n"
+        ));
+    }
+
+    #[test]
+    fn test_register_last() {
+        let mod_rs = register(MOD_RS, 25).unwrap();
+
+        assert!(mod_rs.contains("pub mod d10;\npub mod d25;\npub mod d3;\n"));
+        assert!(mod_rs.contains("    (10, run::<d10::Day10>),\n    (25, run::<d25::Day25>),\n];"));
+    }
+
+    #[test]
+    fn test_register_existing() {
+        assert!(register(MOD_RS, 6).is_err());
+    }
+}
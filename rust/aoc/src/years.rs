@@ -0,0 +1,44 @@
+use aoc_common::solution::{run, Runner};
+
+/// The implemented solutions for a single event year
+pub struct Year {
+    pub year: u16,
+    /// Every implemented solution keyed by its day number, in ascending order
+    pub solutions: &'static [(u8, Runner)],
+}
+
+impl Year {
+    /// Returns the solution runner for the given `day`, if it has been implemented
+    pub fn get(&self, day: u8) -> Option<Runner> {
+        self.solutions
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map(|(_, runner)| *runner)
+    }
+}
+
+/// Every year with at least one solution, in ascending order
+pub const YEARS: &[Year] = &[
+    Year {
+        year: 2021,
+        solutions: &[
+            (1, run::<day01::Day1>),
+            (2, run::<day02::Day2>),
+            (3, run::<day03::Day3>),
+        ],
+    },
+    Year {
+        year: aoc2022::YEAR,
+        solutions: aoc2022::days::SOLUTIONS,
+    },
+];
+
+/// Returns the solutions for the given `year`, if any have been implemented
+pub fn get(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+/// The most recent year with solutions, which is run by default
+pub fn latest() -> &'static Year {
+    &YEARS[YEARS.len() - 1]
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+/// The answer to a single part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// A numeric answer, which is the case for most puzzles
+    Integer(i64),
+    /// A single line of text, such as a code or a list of letters
+    String(String),
+    /// A multi-line picture that has to be read by eye, such as a rendered screen
+    Image(String),
+}
+
+impl Answer {
+    /// The name of the kind of answer, as used in machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "integer",
+            Self::String(_) => "string",
+            Self::Image(_) => "image",
+        }
+    }
+
+    /// Returns `true` if the answer spans multiple lines
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Image(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{s}"),
+            Self::Image(s) => write!(f, "{}", s.trim_end()),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+// answers that do not fit in an `i64` are kept exact by falling back to a string
+macro_rules! impl_from_wide_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    i64::try_from(n).map_or_else(|_| Self::String(n.to_string()), Self::Integer)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_wide_int!(u64, usize, i128, u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(42u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-7i64), Answer::Integer(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::String(u64::MAX.to_string()));
+        assert_eq!(Answer::from("mjqj"), Answer::String("mjqj".to_string()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Integer(13140).to_string(), "13140");
+        assert_eq!(Answer::Image("#.\n.#\n".to_string()).to_string(), "#.\n.#");
+    }
+}
@@ -0,0 +1,60 @@
+//! Argument handling shared by the small tools of each year, which take a few
+//! positional arguments followed by options given as `--flag <value>`.
+
+use std::env;
+use std::error::Error;
+use std::process;
+
+/// Pairs each option flag with its value, failing if the last flag has no value
+pub fn flags(args: &[String]) -> Result<Vec<(&str, &str)>, String> {
+    args.chunks(2)
+        .map(|pair| match pair {
+            [flag, value] => Ok((flag.as_str(), value.as_str())),
+            [flag] => Err(format!("Missing value for {flag}")),
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// The error for an option flag that the tool does not know
+pub fn unknown(flag: &str) -> Box<dyn Error> {
+    format!("Unknown option `{flag}`").into()
+}
+
+/// Runs a tool with its command line arguments, printing the `usage` instead when there
+/// are none or help is asked for. If `run` fails, the error is printed along with the
+/// usage and the process exits with a failure status.
+pub fn main<F>(usage: &str, run: F)
+where
+    F: FnOnce(&[String]) -> Result<(), Box<dyn Error>>,
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{usage}");
+        return;
+    }
+
+    if let Err(e) = run(&args) {
+        eprintln!("Error: {e}\n\n{usage}");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_flags() {
+        let args = strings(&["-r", "10", "--output", "a.png"]);
+        assert_eq!(flags(&args), Ok(vec![("-r", "10"), ("--output", "a.png")]));
+        assert_eq!(flags(&[]), Ok(vec![]));
+
+        let args = strings(&["-r", "10", "--output"]);
+        assert_eq!(flags(&args), Err("Missing value for --output".to_string()));
+    }
+}
@@ -0,0 +1,169 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::input::InputError;
+use crate::parse::ParseError;
+
+/// Any error that can occur while reading, parsing or solving a puzzle
+#[derive(Debug)]
+pub enum AocError {
+    /// The input could not be found or read
+    Io(InputError),
+    /// The input could not be parsed. `line` and `column` count from 1 and `text`
+    /// holds the full line of input in which the error occurred.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        error: ParseError,
+    },
+    /// The input was parsed, but the puzzle could not be solved
+    Solve(String),
+    /// No solution has been implemented for the given year (and day)
+    NoSolution { year: u16, day: Option<u8> },
+}
+
+impl AocError {
+    /// Creates a parse error that occurred within the `text` of the given `line`, at the
+    /// offset the `error` is located at. An error without a location is placed at the
+    /// end of the text if a token is missing, or else at its start.
+    pub fn parse(line: usize, text: &str, error: ParseError) -> Self {
+        let (offset, error) = match error {
+            ParseError::At(offset, error) => (offset.min(text.len()), *error),
+            ParseError::Missing(_) => (text.len(), error),
+            error => (0, error),
+        };
+        let column = text[..offset].chars().count() + 1;
+
+        Self::Parse {
+            line,
+            column,
+            text: text.to_string(),
+            error,
+        }
+    }
+
+    /// Creates a parse error at the byte `offset` into the full `input`
+    pub fn parse_at(input: &str, offset: usize, error: ParseError) -> Self {
+        let offset = offset.min(input.len());
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self::Parse {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            text: input[start..end].trim_end_matches('\r').to_string(),
+            error,
+        }
+    }
+
+    /// Creates a solver error with the given message
+    pub fn solve(message: impl Into<String>) -> Self {
+        Self::Solve(message.into())
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Parse {
+                line,
+                column,
+                text,
+                error,
+            } => {
+                // point at the offending column beneath the line of input
+                let gutter = " ".repeat(line.to_string().len());
+                let pad = " ".repeat(column - 1);
+                write!(
+                    f,
+                    "{error} at line {line}, column {column}\n {gutter} |\n {line} | {text}\n {gutter} | {pad}^"
+                )
+            }
+            Self::Solve(message) => write!(f, "{message}"),
+            Self::NoSolution { year, day: None } => write!(f, "No solutions for {year}"),
+            Self::NoSolution {
+                year,
+                day: Some(day),
+            } => write!(f, "No solution for {year} day {day}"),
+        }
+    }
+}
+
+impl From<InputError> for AocError {
+    fn from(e: InputError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> Self {
+        Self::solve(message)
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        Self::solve(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_column() {
+        let error = ParseError::Invalid("number", "x".to_string()).at(6);
+        let err = AocError::parse(3, "2-4,6-x", error);
+
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: 3,
+                column: 7,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Invalid number: `x` at line 3, column 7\n   |\n 3 | 2-4,6-x\n   |       ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_repeated_token() {
+        // the column comes from the error rather than the first `1` in the line
+        let error = ParseError::Extra("1".to_string()).at(7);
+        let err = AocError::parse(1, "addx 1 1", error);
+
+        assert!(matches!(err, AocError::Parse { column: 8, ref error, .. }
+            if *error == ParseError::Extra("1".to_string())));
+
+        let err = AocError::parse(1, "addx", ParseError::Missing("operand"));
+        assert!(matches!(err, AocError::Parse { column: 5, .. }));
+    }
+
+    #[test]
+    fn test_parse_at() {
+        let input = "a = 1\nb = ?\n";
+        let err = AocError::parse_at(input, 10, ParseError::Syntax("?".to_string()));
+
+        assert!(
+            matches!(err, AocError::Parse { line: 2, column: 5, ref text, .. } if text == "b = ?")
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::error::AocError;
+use crate::parse::ParseError;
+use crate::point::{Bounds, Point};
+
+/// A dense, rectangular 2D grid of cells indexed by [`Point`], where `(0, 0)`
+/// is the upper-left most cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width x height` grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from a list of rows, which must all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseError::Invalid("row length", format!("row {y}")));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses each line of `s` as a row of the grid, converting each character with `f`.
+    /// Errors are reported at the line and column of the offending character.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Self, AocError>
+    where
+        F: FnMut(char) -> Result<T, ParseError>,
+    {
+        let mut rows: Vec<Vec<T>> = vec![];
+
+        for (y, line) in s.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    f(c).map_err(|error| AocError::Parse {
+                        line: y + 1,
+                        column: x + 1,
+                        text: line.to_string(),
+                        error,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if rows.first().is_some_and(|first| first.len() != row.len()) {
+                let error = ParseError::Invalid("row length", row.len().to_string());
+                return Err(AocError::Parse {
+                    line: y + 1,
+                    column: line.chars().count().min(rows[0].len()) + 1,
+                    text: line.to_string(),
+                    error,
+                });
+            }
+            rows.push(row);
+        }
+
+        Ok(Self {
+            width: rows.first().map_or(0, |row| row.len()),
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        let (x, y) = (usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Sets the value of the cell at `p`, returning `false` if it is outside the grid
+    pub fn set(&mut self, p: Point, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterates over each row of the grid from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Iterates over every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| {
+            let p = Point::new((i % width) as i32, (i / width) as i32);
+            (p, cell)
+        })
+    }
+}
+
+impl Grid<bool> {
+    /// Creates the smallest grid containing every point, with those points set. The
+    /// grid is offset so that the upper-left corner of the bounds is at `(0, 0)`.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point> + Clone) -> Self {
+        let Some(bounds) = Bounds::of(points.clone()) else {
+            return Self::new(0, 0, false);
+        };
+        let mut grid = Self::new(bounds.width(), bounds.height(), false);
+
+        for p in points {
+            grid.set(Point::new(p.x - bounds.x_min, p.y - bounds.y_min), true);
+        }
+
+        grid
+    }
+
+    /// Renders the grid as lines of text, using `on` for set cells and `off` otherwise
+    pub fn render(&self, on: char, off: char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            s.extend(row.iter().map(|&cell| if cell { on } else { off }));
+            s.push('\n');
+        }
+
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_cell(c: char) -> Result<bool, ParseError> {
+        match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::Invalid("cell", c.to_string())),
+        }
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let s = "#..\n.#.\n..#\n.##\n";
+        let grid = Grid::parse(s, parse_cell).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&true));
+        assert_eq!(grid.get(Point::new(0, 1)), Some(&false));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.render('#', '.'), s);
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        assert!(Grid::parse("##\n#\n", parse_cell).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_cell() {
+        let err = Grid::parse("#.\n.x\n", parse_cell).unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_from_points() {
+        let points = [Point::new(-1, 5), Point::new(1, 6)];
+        let grid = Grid::from_points(&points);
+
+        assert_eq!(grid.render('#', '.'), "#..\n..#\n");
+    }
+}
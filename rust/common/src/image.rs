@@ -0,0 +1,163 @@
+//! Exports boolean grids, such as screens and maps, as image files. Set cells are
+//! drawn black and unset cells white, with every cell scaled up to a square of
+//! `scale x scale` pixels.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::point::Point;
+
+/// The file format of an exported image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A binary portable bitmap (`P4`)
+    Pbm,
+    /// A binary portable pixmap (`P6`)
+    Ppm,
+    /// An 8-bit grayscale PNG
+    Png,
+}
+
+impl Format {
+    /// Determines the format from the extension of `path`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.to_lowercase().parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(Self::Pbm),
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            _ => Err(ParseError::Invalid("image format", s.to_string())),
+        }
+    }
+}
+
+/// Scales up the grid, returning its dimensions and whether each pixel is set, row by row
+fn pixels(grid: &Grid<bool>, scale: usize) -> (usize, usize, Vec<bool>) {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let pixels = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let p = Point::new((x / scale) as i32, (y / scale) as i32);
+            grid.get(p) == Some(&true)
+        })
+        .collect();
+
+    (width, height, pixels)
+}
+
+/// Encodes the grid as an image in the given `format`, scaling each cell by `scale`
+pub fn encode(grid: &Grid<bool>, format: Format, scale: usize) -> io::Result<Vec<u8>> {
+    if scale == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The scale of an image must be at least 1",
+        ));
+    }
+    let (width, height, pixels) = pixels(grid, scale);
+    let gray = |set: bool| if set { 0 } else { 255 };
+
+    let bytes = match format {
+        Format::Pbm => {
+            let mut bytes = format!("P4\n{width} {height}\n").into_bytes();
+            // each row is packed into bits, with 1 for black, padded to a whole byte
+            for row in pixels.chunks(width.max(1)).take(height) {
+                for byte in row.chunks(8) {
+                    let bits = byte.iter().fold(0u8, |b, &set| (b << 1) | u8::from(set));
+                    bytes.push(bits << (8 - byte.len()));
+                }
+            }
+            bytes
+        }
+        Format::Ppm => {
+            let mut bytes = format!("P6\n{width} {height}\n255\n").into_bytes();
+            bytes.extend(pixels.iter().flat_map(|&set| [gray(set); 3]));
+            bytes
+        }
+        Format::Png => {
+            let mut bytes = vec![];
+            let mut encoder = png::Encoder::new(&mut bytes, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Grayscale);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let data: Vec<u8> = pixels.iter().map(|&set| gray(set)).collect();
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&data)?;
+            writer.finish()?;
+            bytes
+        }
+    };
+
+    Ok(bytes)
+}
+
+/// Writes the grid to `path` as an image, in the format given by its extension
+pub fn save(grid: &Grid<bool>, path: impl AsRef<Path>, scale: usize) -> io::Result<()> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{}: expected an image with a .pbm, .ppm or .png extension",
+                path.display()
+            ),
+        )
+    })?;
+
+    fs::write(path, encode(grid, format, scale)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<bool> {
+        Grid::from_rows(vec![vec![true, false, true], vec![false, true, false]]).unwrap()
+    }
+
+    #[test]
+    fn test_pbm() {
+        let bytes = encode(&grid(), Format::Pbm, 1).unwrap();
+        assert_eq!(bytes, b"P4\n3 2\n\xa0\x40");
+
+        let bytes = encode(&grid(), Format::Pbm, 3).unwrap();
+        assert_eq!(&bytes[..7], b"P4\n9 6\n");
+        // rows of 9 pixels take 2 bytes: `###...###` then padding
+        assert_eq!(&bytes[7..9], &[0b1110_0011, 0b1000_0000]);
+        assert_eq!(bytes.len(), 7 + 6 * 2);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let bytes = encode(&grid(), Format::Ppm, 2).unwrap();
+        assert!(bytes.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(bytes.len(), 11 + 6 * 4 * 3);
+        assert_eq!(&bytes[11..20], &[0, 0, 0, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_png() {
+        let bytes = encode(&grid(), Format::Png, 4).unwrap();
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let reader = decoder.read_info().unwrap();
+
+        assert_eq!((reader.info().width, reader.info().height), (12, 8));
+        assert!(encode(&grid(), Format::Png, 0).is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("crt.PNG")), Some(Format::Png));
+        assert_eq!(Format::from_path(Path::new("grove.txt")), None);
+    }
+}
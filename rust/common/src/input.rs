@@ -0,0 +1,174 @@
+//! Locates puzzle inputs on disk using a shared directory layout:
+//!
+//! ```text
+//! <data dir>/inputs/<year>/<day>.txt        the personal puzzle input
+//! <data dir>/examples/<year>/<day>-<n>.txt  the n-th example from the puzzle text
+//! ```
+//!
+//! Days are not zero-padded (e.g. `inputs/2022/4.txt`). The data directory defaults to
+//! the current directory and can be overridden with `AOC_DATA_DIR`.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The environment variable used to override the data directory
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// Which variant of a day's input to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The personal puzzle input
+    Input,
+    /// The n-th example given in the puzzle description (counting from 1)
+    Example(u32),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Input => write!(f, "input"),
+            Self::Example(n) => write!(f, "example {n}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No file exists at the resolved path. `resolved` is set when the path was
+    /// derived from the data directory layout rather than given explicitly.
+    Missing {
+        path: PathBuf,
+        description: String,
+        resolved: bool,
+    },
+    /// The file exists but could not be read
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl Error for InputError {}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing {
+                path,
+                description,
+                resolved,
+            } => {
+                write!(
+                    f,
+                    "Missing {description}: expected a file at {}",
+                    path.display()
+                )?;
+                if *resolved {
+                    write!(f, " (set {DATA_DIR_VAR} to change the data directory)")?;
+                }
+                Ok(())
+            }
+            Self::Io { path, error } => write!(f, "Failed to read {}: {error}", path.display()),
+        }
+    }
+}
+
+/// Reads the file at `path`, describing it as `description` if it does not exist
+fn read_file(path: PathBuf, description: String, resolved: bool) -> Result<String, InputError> {
+    match read_to_string(&path) {
+        Ok(s) => Ok(s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::Missing {
+            path,
+            description,
+            resolved,
+        }),
+        Err(error) => Err(InputError::Io { path, error }),
+    }
+}
+
+/// Resolves the location of puzzle inputs relative to a data directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolver {
+    root: PathBuf,
+}
+
+impl Resolver {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses the directory given by `AOC_DATA_DIR`, or the current directory if it is unset
+    pub fn from_env() -> Self {
+        match env::var_os(DATA_DIR_VAR) {
+            Some(dir) => Self::new(dir),
+            None => Self::new("."),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the path of the given `source` for a particular `year` and `day`
+    pub fn path(&self, year: u16, day: u8, source: Source) -> PathBuf {
+        match source {
+            Source::Input => self
+                .root
+                .join("inputs")
+                .join(year.to_string())
+                .join(format!("{day}.txt")),
+            Source::Example(n) => self
+                .root
+                .join("examples")
+                .join(year.to_string())
+                .join(format!("{day}-{n}.txt")),
+        }
+    }
+
+    /// Reads the given `source` for a particular `year` and `day`
+    pub fn read(&self, year: u16, day: u8, source: Source) -> Result<String, InputError> {
+        let path = self.path(year, day, source);
+        read_file(path, format!("{source} for {year} day {day}"), true)
+    }
+}
+
+/// Reads the given `source` for a particular `year` and `day` from the default data directory
+pub fn read(year: u16, day: u8, source: Source) -> Result<String, InputError> {
+    Resolver::from_env().read(year, day, source)
+}
+
+/// Reads an input file from an explicit `path`, bypassing the directory layout
+pub fn read_path(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref().to_path_buf();
+    read_file(path, "input file".to_string(), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        let resolver = Resolver::new("/aoc");
+        let path = resolver.path(2022, 4, Source::Input);
+        assert_eq!(path, PathBuf::from("/aoc/inputs/2022/4.txt"));
+    }
+
+    #[test]
+    fn test_example_path() {
+        let resolver = Resolver::new("/aoc");
+        let path = resolver.path(2021, 12, Source::Example(2));
+        assert_eq!(path, PathBuf::from("/aoc/examples/2021/12-2.txt"));
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let resolver = Resolver::new("/this/path/does/not/exist");
+        let err = resolver.read(2022, 4, Source::Example(1)).unwrap_err();
+
+        assert!(matches!(err, InputError::Missing { .. }));
+        assert!(err.to_string().contains("example 1 for 2022 day 4"));
+        assert!(err.to_string().contains("examples/2022/4-1.txt"));
+    }
+}
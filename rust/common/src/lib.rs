@@ -0,0 +1,10 @@
+pub mod answer;
+pub mod args;
+pub mod error;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod solution;
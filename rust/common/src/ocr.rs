@@ -0,0 +1,146 @@
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// The width of a single letter, in pixels
+pub const GLYPH_WIDTH: usize = 4;
+/// The height of a single letter, in pixels
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Letters are separated by a single blank column
+const SPACING: usize = 1;
+
+/// The capital letters known to appear in the dot-matrix screens of the puzzles, each
+/// drawn as rows of `#` (lit) and `.` (unlit) pixels
+const FONT: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Packs the pixels of a glyph into a bitmask, one bit per pixel row by row
+fn encode(pixel: impl Fn(usize, usize) -> bool) -> u32 {
+    let mut mask = 0;
+    for y in 0..GLYPH_HEIGHT {
+        for x in 0..GLYPH_WIDTH {
+            mask = (mask << 1) | u32::from(pixel(x, y));
+        }
+    }
+    mask
+}
+
+fn lookup(mask: u32) -> Option<char> {
+    FONT.iter()
+        .find(|(_, rows)| encode(|x, y| rows[y].as_bytes()[x] == b'#') == mask)
+        .map(|&(c, _)| c)
+}
+
+/// Reads the capital letters spelled out by a dot-matrix screen, such as the CRT of
+/// 2022 day 10. The screen must be exactly one letter high and hold at least one
+/// letter, with each letter followed by a single blank column (which may be omitted
+/// after the last letter).
+pub fn read(grid: &Grid<bool>) -> Result<String, AocError> {
+    if grid.height() != GLYPH_HEIGHT {
+        return Err(AocError::solve(format!(
+            "Cannot read letters from a screen {} pixels high, expected {GLYPH_HEIGHT}",
+            grid.height()
+        )));
+    }
+
+    let pitch = GLYPH_WIDTH + SPACING;
+    if grid.width() < GLYPH_WIDTH || !matches!(grid.width() % pitch, 0 | GLYPH_WIDTH) {
+        return Err(AocError::solve(format!(
+            "Cannot read letters from a screen {} pixels wide, expected a multiple of {pitch}",
+            grid.width()
+        )));
+    }
+
+    (0..(grid.width() + SPACING) / pitch)
+        .map(|i| {
+            let left = i * pitch;
+            let mask = encode(|x, y| {
+                let p = Point::new((left + x) as i32, y as i32);
+                grid.get(p).copied().unwrap_or(false)
+            });
+
+            lookup(mask).ok_or_else(|| {
+                AocError::solve(format!("Unrecognised letter at column {}", left + 1))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+
+    fn parse_screen(s: &str) -> Grid<bool> {
+        Grid::parse(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::Invalid("pixel", c.to_string())),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_read() {
+        let screen = parse_screen(
+            "\
+###..#..#.####.
+#..#.#..#.#....
+#..#.####.###..
+###..#..#.#....
+#.#..#..#.#....
+#..#.#..#.####.
+",
+        );
+
+        assert_eq!(read(&screen).unwrap(), "RHE");
+    }
+
+    #[test]
+    fn test_read_font() {
+        for &(c, rows) in FONT {
+            assert_eq!(
+                read(&parse_screen(&rows.join("\n"))).unwrap(),
+                c.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_unknown() {
+        let screen = parse_screen("#..#\n.##.\n#..#\n#..#\n.##.\n#..#\n");
+        assert!(read(&screen).is_err());
+        assert!(read(&Grid::new(5, 5, false)).is_err());
+    }
+
+    #[test]
+    fn test_read_width() {
+        // too narrow for a single letter
+        for width in [0, 3] {
+            assert!(read(&Grid::new(width, GLYPH_HEIGHT, false)).is_err());
+        }
+        // part of a letter left over after the blank column
+        let screen = parse_screen(&FONT[0].1.map(|row| format!("{row}.#")).join("\n"));
+        assert!(read(&screen).is_err());
+
+        let screen = parse_screen(&FONT[0].1.map(|row| format!("{row}.")).join("\n"));
+        assert_eq!(read(&screen).unwrap(), "A");
+    }
+}
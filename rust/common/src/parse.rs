@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AocError;
+
+/// Describes why a piece of puzzle input could not be parsed. The `&'static str`
+/// names the kind of token involved, e.g. `"command"` or `"value"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// An expected token is missing
+    Missing(&'static str),
+    /// A token is present but is not valid
+    Invalid(&'static str, String),
+    /// There is unexpected input left over after parsing
+    Extra(String),
+    /// The input does not follow the expected grammar, as described by the message
+    Syntax(String),
+    /// Another error, located at a byte offset into the text being parsed
+    At(usize, Box<ParseError>),
+}
+
+impl ParseError {
+    /// Locates the error at the byte `offset` into the text being parsed, replacing
+    /// any location it already had
+    pub fn at(self, offset: usize) -> Self {
+        match self {
+            Self::At(_, error) => Self::At(offset, error),
+            error => Self::At(offset, Box::new(error)),
+        }
+    }
+
+    /// Moves a located error `by` bytes further into the text, for an error from parsing
+    /// a part of the text that starts `by` bytes in. An error without a location is left
+    /// as it is.
+    pub fn offset_by(self, by: usize) -> Self {
+        match self {
+            Self::At(offset, error) => Self::At(offset + by, error),
+            error => error,
+        }
+    }
+}
+
+/// The byte offset of `part` within `s`, which `part` must be a slice of, such as a
+/// token returned by `split_whitespace`
+pub fn offset_of(s: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(s.as_ptr() as usize);
+    debug_assert!(offset <= s.len(), "`{part}` is not a slice of `{s}`");
+    offset
+}
+
+impl Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing(kind) => write!(f, "Missing {kind}"),
+            Self::Invalid(kind, s) => write!(f, "Invalid {kind}: `{s}`"),
+            Self::Extra(s) => write!(f, "Unexpected extra input: `{s}`"),
+            Self::Syntax(message) => write!(f, "{message}"),
+            Self::At(_, error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// Parses every non-empty line of the input with `f`, reporting the location of
+/// the first line that could not be parsed. Each line is trimmed before it is parsed.
+pub fn lines_with<T, F>(s: &str, mut f: F) -> Result<Vec<T>, AocError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let start = line.len() - line.trim_start().len();
+            f(line.trim()).map_err(|e| AocError::parse(i + 1, line, e.offset_by(start)))
+        })
+        .collect()
+}
+
+/// Parses every non-empty line of the input as a `T`
+pub fn lines<T: FromStr>(s: &str) -> Result<Vec<T>, AocError> {
+    lines_with(s, |line| {
+        line.parse()
+            .map_err(|_| ParseError::Invalid("line", line.to_string()).at(0))
+    })
+}
+
+/// Parses each character of `s` as a digit in the given `radix`
+pub fn digits(s: &str, radix: u32) -> Result<Vec<u32>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| {
+            c.to_digit(radix)
+                .ok_or_else(|| ParseError::Invalid("digit", c.to_string()).at(i))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let values: Vec<i32> = lines("1\n-2\n\n3\n").unwrap();
+        assert_eq!(values, vec![1, -2, 3]);
+    }
+
+    #[test]
+    fn test_lines_invalid() {
+        let err = lines::<i32>("1\n\n x\n").unwrap_err();
+
+        match err {
+            AocError::Parse {
+                line,
+                column,
+                error,
+                ..
+            } => {
+                assert_eq!((line, column), (3, 2));
+                assert_eq!(error, ParseError::Invalid("line", "x".to_string()));
+            }
+            _ => panic!("expected a parse error, found {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits("0110", 2).unwrap(), vec![0, 1, 1, 0]);
+        assert_eq!(
+            digits("0121", 2),
+            Err(ParseError::Invalid("digit", "2".to_string()).at(2))
+        );
+    }
+
+    #[test]
+    fn test_lines_with_offset() {
+        // the error is located past the leading whitespace that was trimmed
+        let err = lines_with("  1 1", |_| {
+            Err::<(), _>(ParseError::Extra("1".to_string()).at(2))
+        })
+        .unwrap_err();
+
+        assert!(
+            matches!(err, AocError::Parse { line: 1, column: 5, ref error, .. }
+            if *error == ParseError::Extra("1".to_string()))
+        );
+    }
+}
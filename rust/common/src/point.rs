@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+/// A point on a 2D grid. The origin is the upper-left most point, with `x` increasing
+/// to the East and `y` increasing to the South.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the point directly to the north of this point
+    pub fn north(&self) -> Self {
+        Self {
+            x: self.x,
+            y: self.y - 1,
+        }
+    }
+
+    /// Returns the point directly to the north east of this point
+    pub fn north_east(&self) -> Self {
+        Self {
+            x: self.x + 1,
+            y: self.y - 1,
+        }
+    }
+
+    /// Returns the point directly to the east of this point
+    pub fn east(&self) -> Self {
+        Self {
+            x: self.x + 1,
+            y: self.y,
+        }
+    }
+
+    /// Returns the point directly to the south east of this point
+    pub fn south_east(&self) -> Self {
+        Self {
+            x: self.x + 1,
+            y: self.y + 1,
+        }
+    }
+
+    /// Returns the point directly to the south of this point
+    pub fn south(&self) -> Self {
+        Self {
+            x: self.x,
+            y: self.y + 1,
+        }
+    }
+
+    /// Returns the point directly to the south west of this point
+    pub fn south_west(&self) -> Self {
+        Self {
+            x: self.x - 1,
+            y: self.y + 1,
+        }
+    }
+
+    /// Returns the point directly to the west of this point
+    pub fn west(&self) -> Self {
+        Self {
+            x: self.x - 1,
+            y: self.y,
+        }
+    }
+
+    /// Returns the point directly to the north west of this point
+    pub fn north_west(&self) -> Self {
+        Self {
+            x: self.x - 1,
+            y: self.y - 1,
+        }
+    }
+
+    /// Returns all 8 points that are adjacent to the point instance
+    pub fn adjacent_points(&self) -> HashSet<Self> {
+        HashSet::from_iter([
+            self.north(),
+            self.north_east(),
+            self.east(),
+            self.south_east(),
+            self.south(),
+            self.south_west(),
+            self.west(),
+            self.north_west(),
+        ])
+    }
+
+    /// Returns the 3 points adjacent to this point in the NW, N, NE directions
+    pub fn adjacent_north(&self) -> HashSet<Self> {
+        HashSet::from_iter([self.north_west(), self.north(), self.north_east()])
+    }
+
+    /// Returns the 3 points adjacent to this point in the NE, E, SE directions
+    pub fn adjacent_east(&self) -> HashSet<Self> {
+        HashSet::from_iter([self.north_east(), self.east(), self.south_east()])
+    }
+
+    /// Returns the 3 points adjacent to this point in the SE, S, SW directions
+    pub fn adjacent_south(&self) -> HashSet<Self> {
+        HashSet::from_iter([self.south_east(), self.south(), self.south_west()])
+    }
+
+    /// Returns the 3 points adjacent to this point in the SW, W, NW directions
+    pub fn adjacent_west(&self) -> HashSet<Self> {
+        HashSet::from_iter([self.south_west(), self.west(), self.north_west()])
+    }
+}
+
+/// The smallest rectangle (inclusive on every side) containing a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x_min: i32,
+    pub x_max: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+}
+
+impl Bounds {
+    /// Returns the bounds of the given points, or `None` if there are no points
+    pub fn of<'a>(points: impl IntoIterator<Item = &'a Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let init = Self {
+            x_min: first.x,
+            x_max: first.x,
+            y_min: first.y,
+            y_max: first.y,
+        };
+
+        Some(points.fold(init, |b, p| Self {
+            x_min: b.x_min.min(p.x),
+            x_max: b.x_max.max(p.x),
+            y_min: b.y_min.min(p.y),
+            y_max: b.y_max.max(p.y),
+        }))
+    }
+
+    // +1 to count each grid unit as a "tile" rather than a point
+    // since the distance between two points constitutes a single "tile"
+    pub fn width(&self) -> usize {
+        (self.x_max - self.x_min + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.y_max - self.y_min + 1) as usize
+    }
+
+    /// The number of tiles covered by the bounds
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (self.x_min..=self.x_max).contains(&p.x) && (self.y_min..=self.y_max).contains(&p.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjacent_points() {
+        let p = Point::new(0, 0);
+        let adjacent = p.adjacent_points();
+
+        assert_eq!(adjacent.len(), 8);
+        assert!(!adjacent.contains(&p));
+        assert!(p.adjacent_north().is_subset(&adjacent));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let points = [Point::new(2, 1), Point::new(-1, 4), Point::new(0, 0)];
+        let bounds = Bounds::of(&points).unwrap();
+
+        assert_eq!((bounds.x_min, bounds.x_max), (-1, 2));
+        assert_eq!((bounds.y_min, bounds.y_max), (0, 4));
+        assert_eq!(bounds.area(), 20);
+        assert!(bounds.contains(&Point::new(1, 3)));
+        assert!(!bounds.contains(&Point::new(3, 3)));
+    }
+
+    #[test]
+    fn test_bounds_empty() {
+        assert_eq!(Bounds::of(&[]), None);
+    }
+}
@@ -0,0 +1,203 @@
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+
+/// A solution to a single day's puzzle.
+///
+/// The raw puzzle input is parsed once with [`Solution::parse`] and the parsed
+/// value is then shared by both parts of the puzzle.
+pub trait Solution {
+    /// The parsed representation of the puzzle input
+    type Input;
+    /// The answer type for the first part of the puzzle
+    type Part1: Into<Answer>;
+    /// The answer type for the second part of the puzzle
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError>;
+}
+
+/// Which parts of a puzzle to solve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Parts {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+impl Parts {
+    /// Returns `true` if the given `part` (1 or 2) should be solved
+    pub fn includes(&self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Self::Both, _) | (Self::Part1, 1) | (Self::Part2, 2)
+        )
+    }
+}
+
+/// The outcome of each part of a puzzle that was attempted. Each part succeeds or
+/// fails on its own, so an unsolved part never hides the answer to the other.
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub part1: Option<Result<Answer, AocError>>,
+    pub part2: Option<Result<Answer, AocError>>,
+}
+
+impl Answers {
+    /// Iterates over the outcome of each attempted part along with its part number
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Result<Answer, AocError>)> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_ref()?)))
+    }
+}
+
+/// The time taken by each stage of a single run of a solution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Solves a single part of the puzzle, timing how long it takes if it succeeds
+fn timed<T: Into<Answer>>(
+    f: impl FnOnce() -> Result<T, AocError>,
+) -> (Result<Answer, AocError>, Option<Duration>) {
+    let start = Instant::now();
+    match f() {
+        Ok(answer) => (Ok(answer.into()), Some(start.elapsed())),
+        Err(e) => (Err(e), None),
+    }
+}
+
+/// Parses the given `input` and solves the selected `parts` of the puzzle using the
+/// solution `S`, timing each of the stages separately. Only a failure to parse is an
+/// error; the failure of a part is recorded in its place among the answers.
+pub fn run<S: Solution>(input: &str, parts: Parts) -> Result<(Answers, Timings), AocError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let mut answers = Answers::default();
+    let mut timings = Timings {
+        parse,
+        ..Default::default()
+    };
+
+    if parts.includes(1) {
+        let (answer, time) = timed(|| S::part1(&parsed));
+        answers.part1 = Some(answer);
+        timings.part1 = time;
+    }
+
+    if parts.includes(2) {
+        let (answer, time) = timed(|| S::part2(&parsed));
+        answers.part2 = Some(answer);
+        timings.part2 = time;
+    }
+
+    Ok((answers, timings))
+}
+
+/// A type-erased [`run`] for a particular solution, used to dispatch by day number
+pub type Runner = fn(&str, Parts) -> Result<(Answers, Timings), AocError>;
+
+/// Runs the full pipeline of the solution `S` for a single `part` of an example and
+/// asserts that it produces the `expected` answer. Answers are compared by their
+/// rendered text (ignoring trailing whitespace), so an image answer can be given as a
+/// string. Used by [`aoc_example!`].
+///
+/// [`aoc_example!`]: crate::aoc_example
+pub fn check_example<S: Solution>(input: &str, part: u8, expected: impl Into<Answer>) {
+    let parts = match part {
+        1 => Parts::Part1,
+        _ => Parts::Part2,
+    };
+    let fail = |e: &AocError| -> ! { panic!("part {part} of the example failed: {e}") };
+    let (answers, _) = run::<S>(input, parts).unwrap_or_else(|e| fail(&e));
+    let actual = answers
+        .iter()
+        .next()
+        .map(|(_, answer)| answer.as_ref().unwrap_or_else(|e| fail(e)).to_string());
+    let expected = expected.into().to_string();
+
+    assert_eq!(
+        actual.as_deref().map(str::trim_end),
+        Some(expected.trim_end()),
+        "wrong answer to part {part} of the example"
+    );
+}
+
+/// Defines a test that runs a solution end to end against an example from the puzzle
+/// text, checking the answer to each of the given parts:
+///
+/// ```ignore
+/// aoc_example!(Day4, EXAMPLE, part1 = 2, part2 = 4);
+/// ```
+///
+/// The test is named `example`, unless a name is given with `name: Day`. Attributes
+/// such as `#[ignore]` may precede the name.
+#[macro_export]
+macro_rules! aoc_example {
+    (@part part1) => { 1 };
+    (@part part2) => { 2 };
+    ($(#[$attr:meta])* $name:ident: $day:ty, $input:expr, $($part:ident = $expected:expr),+ $(,)?) => {
+        #[test]
+        $(#[$attr])*
+        fn $name() {
+            $(
+                $crate::solution::check_example::<$day>(
+                    $input,
+                    $crate::aoc_example!(@part $part),
+                    $expected,
+                );
+            )+
+        }
+    };
+    ($(#[$attr:meta])* $day:ty, $input:expr, $($rest:tt)+) => {
+        $crate::aoc_example!($(#[$attr])* example: $day, $input, $($rest)+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        type Input = i64;
+        type Part1 = i64;
+        type Part2 = i64;
+
+        fn parse(input: &str) -> Result<Self::Input, AocError> {
+            input
+                .trim()
+                .parse()
+                .map_err(|_| AocError::solve("Not a number"))
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Part1, AocError> {
+            Ok(input * 2)
+        }
+
+        fn part2(_: &Self::Input) -> Result<Self::Part2, AocError> {
+            Err(AocError::solve("Part II has not been solved yet"))
+        }
+    }
+
+    #[test]
+    fn test_run_part_fails() {
+        let (answers, timings) = run::<Unfinished>("21", Parts::Both).unwrap();
+
+        assert_eq!(answers.part1.unwrap().unwrap(), Answer::Integer(42));
+        assert!(answers.part2.unwrap().is_err());
+        assert!(timings.part1.is_some() && timings.part2.is_none());
+        assert!(run::<Unfinished>("x", Parts::Both).is_err());
+    }
+}